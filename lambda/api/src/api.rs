@@ -1,24 +1,137 @@
-use crate::requests::{SayGoodbyeRequest, SayHelloRequest};
+use crate::requests::{
+    GetAccountProfileRequest, GetAccountRequest, GetGameRequest, GetGameRoundsRequest,
+    GetLeaderboardRequest, GetSessionDetailsRequest, SayGoodbyeRequest, SayHelloRequest,
+};
+use crate::router::{self, Params, Response, Route, Router};
 use domain::errors::LogicError;
+use serde_json::Value;
 use service;
+use std::collections::HashMap;
 
+const MAX_RETRIES: usize = 10;
+
+/// The authenticated user making a REST request, from the Cognito claims.
+pub struct Caller {
+    pub account_id: String,
+    pub email: String,
+    pub username: String,
+}
+
+impl Caller {
+    /// Reads the caller from Cognito ID token claims, as passed on by the
+    /// API Gateway authorizer.
+    pub fn from_claims(claims: &Value) -> Result<Self, LogicError> {
+        let claim = |name: &str| {
+            claims[name]
+                .as_str()
+                .map(|s| s.to_string())
+                .ok_or(LogicError::RestError(format!("No {name}")))
+        };
+        Ok(Caller {
+            account_id: claim("sub")?,
+            email: claim("email")?,
+            username: claim("cognito:username")?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum HandlerType {
+    Account,
+    Game,
+    GameRounds,
     Goodbye,
+    Health,
     Hello,
+    Leaderboard,
+    Profile,
+    Session,
 }
 
-impl HandlerType {
-    pub fn from_str(s: &str) -> Result<HandlerType, String> {
-        match s {
-            "/v1/hello" => Ok(HandlerType::Hello),
-            "/v1/goodbye" => Ok(HandlerType::Goodbye),
-            _ => Err("Invalid handler type".to_owned()),
+fn router() -> Router<HandlerType> {
+    let route = |method, template, handler| Route {
+        method,
+        template,
+        handler,
+    };
+    Router::new(vec![
+        route("GET", "/v1/accounts/me", HandlerType::Account),
+        route("GET", "/v1/games/{game_id}", HandlerType::Game),
+        route("GET", "/v1/games/{game_id}/rounds", HandlerType::GameRounds),
+        route("POST", "/v1/goodbye", HandlerType::Goodbye),
+        route("GET", "/v1/health", HandlerType::Health),
+        route("POST", "/v1/hello", HandlerType::Hello),
+        route("GET", "/v1/leaderboard", HandlerType::Leaderboard),
+        route("GET", "/v1/profile", HandlerType::Profile),
+        route("GET", "/v1/sessions/{session_id}", HandlerType::Session),
+    ])
+}
+
+pub fn find_route(
+    method: &str,
+    path: &str,
+    query: HashMap<String, String>,
+) -> Result<(HandlerType, Params), LogicError> {
+    router().find(method, path, query)
+}
+
+/// Routes a REST request and builds the response, retrying handlers that
+/// lose an optimistic locking race.
+pub async fn handle(
+    method: &str,
+    path: &str,
+    query: HashMap<String, String>,
+    body: &[u8],
+    caller: &Caller,
+) -> Response {
+    let (handler_type, params) = match find_route(method, path, query) {
+        Ok(route) => route,
+        Err(e) => return router::to_response(Err(e)),
+    };
+    if handler_type == HandlerType::Health {
+        return health_response(service::health::handler().await);
+    }
+    for _ in 0..MAX_RETRIES {
+        let result = route(&handler_type, &params, body, caller).await;
+        if let Err(LogicError::ConditionalCheckFailed(_)) = result {
+            continue;
         }
+        return router::to_response(result);
     }
+    router::to_response(Err(LogicError::ConditionalCheckFailed(
+        "Max retries reached".into(),
+    )))
 }
 
-pub async fn route(handler_type: &HandlerType, body: &[u8]) -> Result<String, LogicError> {
+pub async fn route(
+    handler_type: &HandlerType,
+    params: &Params,
+    body: &[u8],
+    caller: &Caller,
+) -> Result<String, LogicError> {
     match handler_type {
+        HandlerType::Account => {
+            let request = GetAccountRequest {};
+            let command = request.to_command(caller)?;
+            service::view_account::handler(&command).await
+        }
+        HandlerType::Game => {
+            let request = GetGameRequest {
+                game_id: params.path("game_id")?,
+            };
+            let command = request.to_command(&caller.account_id)?;
+            service::view_game::handler(&command).await
+        }
+        HandlerType::GameRounds => {
+            let request = GetGameRoundsRequest {
+                game_id: params.path("game_id")?,
+                offset: params.query_parsed("offset")?,
+                limit: params.query_parsed("limit")?,
+            };
+            let command = request.to_command(&caller.account_id)?;
+            service::view_game::rounds(&command).await
+        }
+        HandlerType::Health => Ok(service::health::handler().await?.body),
         HandlerType::Hello => {
             let request = deserialise_body::<SayHelloRequest>(body)?;
             let command = request.to_command();
@@ -29,6 +142,39 @@ pub async fn route(handler_type: &HandlerType, body: &[u8]) -> Result<String, Lo
             let command = request.to_command();
             service::goodbye::handler(&command).await
         }
+        HandlerType::Leaderboard => {
+            let request = GetLeaderboardRequest {
+                stat: params.query("stat").unwrap_or_default(),
+                game_id: params.query("gameId"),
+                offset: params.query_parsed("offset")?,
+                limit: params.query_parsed("limit")?,
+            };
+            let command = request.to_query()?;
+            service::get_leaderboard::query(&command).await
+        }
+        HandlerType::Profile => {
+            let request = GetAccountProfileRequest {};
+            let command = request.to_command(&caller.account_id)?;
+            service::get_profile::get_for_account(&command).await
+        }
+        HandlerType::Session => {
+            let request = GetSessionDetailsRequest {
+                session_id: params.path("session_id")?,
+            };
+            let command = request.to_command(&caller.account_id)?;
+            service::view_session::handler(&command).await
+        }
+    }
+}
+
+/// Answers 503 with the full report when a dependency can't be reached.
+fn health_response(result: Result<service::health::Health, LogicError>) -> Response {
+    match result {
+        Ok(health) => Response {
+            status: if health.healthy { 200 } else { 503 },
+            body: health.body,
+        },
+        Err(e) => router::to_response(Err(e)),
     }
 }
 
@@ -38,3 +184,33 @@ where
 {
     serde_json::from_slice(body_str).map_err(|e| LogicError::DeserializationError(e.to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_caller_from_claims() {
+        let claims = json!({
+            "sub": "abc-123",
+            "email": "player@example.com",
+            "cognito:username": "player",
+        });
+        let caller = Caller::from_claims(&claims).unwrap();
+        assert_eq!(caller.account_id, "abc-123");
+        assert_eq!(caller.username, "player");
+        assert!(Caller::from_claims(&json!({"sub": "abc-123"})).is_err());
+    }
+
+    #[test]
+    fn answers_unavailable_when_degraded() {
+        let health = service::health::Health {
+            healthy: false,
+            body: r#"{"status":"degraded"}"#.to_string(),
+        };
+        let response = health_response(Ok(health));
+        assert_eq!(response.status, 503);
+        assert_eq!(response.body, r#"{"status":"degraded"}"#);
+    }
+}
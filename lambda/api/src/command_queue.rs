@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use tokio::sync::Mutex as AsyncMutex;
+
+static QUEUE: OnceLock<KeyedQueue> = OnceLock::new();
+
+pub fn get() -> &'static KeyedQueue {
+    QUEUE.get_or_init(KeyedQueue::default)
+}
+
+/// Runs commands one at a time per key, in the order they arrived, while
+/// commands with no key in common run concurrently. Each process has its own
+/// queue, so this orders nothing across Lambda instances.
+#[derive(Default)]
+pub struct KeyedQueue {
+    keys: Mutex<HashMap<String, Weak<AsyncMutex<()>>>>,
+}
+
+impl KeyedQueue {
+    pub async fn run<F: Future>(&self, keys: &[String], command: F) -> F::Output {
+        let mut keys = keys.to_vec();
+        // Always locking in the same order means two commands can't each
+        // hold a key the other is waiting for
+        keys.sort();
+        keys.dedup();
+        let mut guards = vec![];
+        for key in keys {
+            let lock = self.lock_for(&key);
+            guards.push(lock.lock_owned().await);
+        }
+        let output = command.await;
+        drop(guards);
+        self.forget_unused();
+        output
+    }
+
+    /// The keys with a command running or waiting.
+    pub fn busy_keys(&self) -> Vec<String> {
+        let keys = self.keys.lock().unwrap();
+        let mut busy: Vec<String> = keys
+            .iter()
+            .filter(|(_, lock)| lock.strong_count() > 0)
+            .map(|(key, _)| key.clone())
+            .collect();
+        busy.sort();
+        busy
+    }
+
+    fn lock_for(&self, key: &str) -> Arc<AsyncMutex<()>> {
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(lock) = keys.get(key).and_then(|lock| lock.upgrade()) {
+            return lock;
+        }
+        let lock = Arc::new(AsyncMutex::new(()));
+        keys.insert(key.to_string(), Arc::downgrade(&lock));
+        lock
+    }
+
+    fn forget_unused(&self) {
+        let mut keys = self.keys.lock().unwrap();
+        keys.retain(|_, lock| lock.strong_count() > 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_commands_for_a_key_in_order() {
+        let queue = KeyedQueue::default();
+        let order = Mutex::new(vec![]);
+        let keys = vec!["connection:a".to_string()];
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        // Joined futures are first polled in order, so each command reaches
+        // the queue before the next. Later commands would overtake the first,
+        // still waiting to be released, if not queued.
+        let first = queue.run(&keys, async {
+            receiver.await.unwrap();
+            order.lock().unwrap().push(0);
+        });
+        let second = queue.run(&keys, async { order.lock().unwrap().push(1) });
+        let third = queue.run(&keys, async { order.lock().unwrap().push(2) });
+        let release = async { sender.send(()).unwrap() };
+
+        tokio::join!(first, second, third, release);
+
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+        assert!(queue.busy_keys().is_empty());
+    }
+
+    #[tokio::test]
+    async fn runs_commands_without_shared_keys_concurrently() {
+        let queue = KeyedQueue::default();
+        let a = vec!["connection:a".to_string(), "game:ABCD".to_string()];
+        let b = vec!["connection:b".to_string()];
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        let blocked = queue.run(&a, receiver);
+        let free = async {
+            assert_eq!(queue.busy_keys(), vec!["connection:a", "game:ABCD"]);
+            queue.run(&b, async { sender.send(()).unwrap() }).await;
+        };
+        let (result, _) = tokio::join!(blocked, free);
+        assert!(result.is_ok());
+    }
+}
@@ -1,3 +1,7 @@
 pub mod api;
+pub mod command_queue;
+pub mod rate_limit;
 pub mod requests;
+pub mod router;
+pub mod sqs;
 pub mod websocket;
@@ -0,0 +1,142 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use config::RateLimit;
+use domain::errors::LogicError;
+use domain::values::ConnectionId;
+use notifier::{ActionType, INotifier, Message, RateLimitedMessage};
+use std::time::Duration;
+use storage::rate_limit_table::RateLimitItem;
+use storage::websocket_table::WebsocketItem;
+use storage::IDynamoDbClient;
+
+// Losing a race for a bucket means someone else is spending it quickly
+const MAX_ATTEMPTS: usize = 3;
+
+// How long after filling up again a bucket is kept
+const EXPIRY_MARGIN_SECS: i64 = 60;
+
+/// Spends a token for the action from the connection's bucket and, once the
+/// connection is bound to one, the session's bucket, so reconnecting doesn't
+/// reset the limit. A limited client is told how long to wait.
+pub async fn enforce(action: &str, connection_id: &str) -> Result<(), LogicError> {
+    let keys = bucket_keys(connection_id).await?;
+    let Some(retry_after) = try_take(action, &keys).await? else {
+        return Ok(());
+    };
+    println!("Rate limited {action} for {connection_id}");
+    let notifier = notifier::get().await;
+    let message = Message::new_err(ActionType::RateLimited(RateLimitedMessage {
+        action: action.to_string(),
+        retry_after_ms: retry_after.as_millis() as u64,
+    }));
+    // The request is refused either way
+    let notified = match ConnectionId::parse(connection_id) {
+        Ok(connection_id) => notifier.notify(&connection_id, &message).await,
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = notified {
+        println!("Failed to notify {connection_id} of rate limit: {e}");
+    }
+    Err(LogicError::RateLimited(format!(
+        "{action}, retry after {}ms",
+        retry_after.as_millis()
+    )))
+}
+
+/// Session IDs sent by clients aren't checked yet, so only the session bound
+/// to the connection is charged. Otherwise anyone knowing a session ID could
+/// drain its bucket.
+async fn bucket_keys(connection_id: &str) -> Result<Vec<String>, LogicError> {
+    let mut keys = vec![format!("connection:{connection_id}")];
+    let db = storage::get().await;
+    match WebsocketItem::from_db(connection_id, &db).await {
+        Ok(WebsocketItem {
+            session_id: Some(session_id),
+            ..
+        }) => keys.push(format!("session:{session_id}")),
+        Ok(_) | Err(LogicError::ItemNotFound(_)) => {}
+        Err(e) => return Err(e),
+    }
+    Ok(keys)
+}
+
+/// Returns how long to wait if any bucket is empty, taking nothing.
+pub async fn try_take(action: &str, keys: &[String]) -> Result<Option<Duration>, LogicError> {
+    let limit = config::get().rate_limits.for_action(action);
+    let db = storage::get().await;
+    for _ in 0..MAX_ATTEMPTS {
+        let now = Utc::now();
+        let mut writes = vec![];
+        for key in keys {
+            let bucket_id = format!("{key}#{action}");
+            let mut bucket = RateLimitItem::from_db_or_new(&bucket_id, limit.requests, &db).await?;
+            if let Err(retry_after) = take(&mut bucket, &limit, now) {
+                return Ok(Some(retry_after));
+            }
+            writes.push(bucket.save()?);
+        }
+        match db.write(writes).await {
+            Err(LogicError::ConditionalCheckFailed(_)) => continue,
+            result => return result.map(|_| None),
+        }
+    }
+    Ok(Some(limit.refill_interval()))
+}
+
+fn take(bucket: &mut RateLimitItem, limit: &RateLimit, now: DateTime<Utc>) -> Result<(), Duration> {
+    let interval = TimeDelta::from_std(limit.refill_interval()).unwrap_or(TimeDelta::max_value());
+    let elapsed = now - bucket.refilled_at;
+    let refills = elapsed.num_microseconds().unwrap_or(i64::MAX)
+        / interval.num_microseconds().unwrap_or(i64::MAX).max(1);
+    if refills > 0 {
+        let tokens = (bucket.tokens as i64).saturating_add(refills);
+        bucket.tokens = tokens.min(limit.requests as i64) as i32;
+        bucket.refilled_at = match bucket.tokens == limit.requests {
+            true => now,
+            false => bucket.refilled_at + interval * refills as i32,
+        };
+    }
+    // The limit may have been lowered since the bucket was filled
+    bucket.tokens = bucket.tokens.min(limit.requests);
+    if bucket.tokens < 1 {
+        let next_refill = bucket.refilled_at + interval;
+        return Err((next_refill - now).to_std().unwrap_or_default());
+    }
+    bucket.tokens -= 1;
+    // Left alone, the bucket is full again by then
+    bucket.expires_at = interval
+        .checked_mul(limit.requests - bucket.tokens)
+        .and_then(|until_full| until_full.checked_add(&TimeDelta::seconds(EXPIRY_MARGIN_SECS)))
+        .and_then(|ttl| bucket.refilled_at.checked_add_signed(ttl));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refills_one_token_per_interval() {
+        let limit = RateLimit::new(2, 10);
+        let start = Utc::now();
+        let mut bucket = RateLimitItem::new("connection:a#rollDice", limit.requests);
+        bucket.refilled_at = start;
+
+        assert!(take(&mut bucket, &limit, start).is_ok());
+        assert!(take(&mut bucket, &limit, start).is_ok());
+        let full_at = start + TimeDelta::seconds(10);
+        assert_eq!(
+            bucket.expires_at,
+            Some(full_at + TimeDelta::seconds(EXPIRY_MARGIN_SECS))
+        );
+        let retry_after = take(&mut bucket, &limit, start + TimeDelta::seconds(1)).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(4));
+
+        assert!(take(&mut bucket, &limit, start + TimeDelta::seconds(5)).is_ok());
+        assert!(take(&mut bucket, &limit, start + TimeDelta::seconds(6)).is_err());
+        // A long wait only refills up to the limit
+        let later = start + TimeDelta::minutes(5);
+        assert!(take(&mut bucket, &limit, later).is_ok());
+        assert!(take(&mut bucket, &limit, later).is_ok());
+        assert!(take(&mut bucket, &limit, later).is_err());
+    }
+}
@@ -1,4 +1,10 @@
+use crate::api::Caller;
 use domain::commands;
+use domain::errors::LogicError;
+use domain::values::{
+    AccountId, ConnectionId, GameCode, IdToken, LeaderboardStat, Nickname, Page, ReconnectToken,
+    SessionId,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -26,13 +32,111 @@ impl SayGoodbyeRequest {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetAccountRequest {}
+impl GetAccountRequest {
+    pub fn to_command(&self, caller: &Caller) -> Result<commands::GetAccountCommand, LogicError> {
+        Ok(commands::GetAccountCommand {
+            account_id: AccountId::parse(&caller.account_id)?,
+            email: caller.email.clone(),
+            username: caller.username.clone(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetGameRequest {
+    #[serde(rename = "gameId")]
+    pub game_id: String,
+}
+impl GetGameRequest {
+    pub fn to_command(&self, account_id: &str) -> Result<commands::GetGameCommand, LogicError> {
+        Ok(commands::GetGameCommand {
+            account_id: AccountId::parse(account_id)?,
+            game_id: GameCode::parse(&self.game_id)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetGameRoundsRequest {
+    #[serde(rename = "gameId")]
+    pub game_id: String,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+impl GetGameRoundsRequest {
+    pub fn to_command(
+        &self,
+        account_id: &str,
+    ) -> Result<commands::GetGameRoundsCommand, LogicError> {
+        Ok(commands::GetGameRoundsCommand {
+            account_id: AccountId::parse(account_id)?,
+            game_id: GameCode::parse(&self.game_id)?,
+            page: Page::parse(self.offset, self.limit)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetSessionDetailsRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+}
+impl GetSessionDetailsRequest {
+    pub fn to_command(
+        &self,
+        account_id: &str,
+    ) -> Result<commands::GetSessionDetailsCommand, LogicError> {
+        Ok(commands::GetSessionDetailsCommand {
+            account_id: AccountId::parse(account_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetAccountProfileRequest {}
+impl GetAccountProfileRequest {
+    pub fn to_command(
+        &self,
+        account_id: &str,
+    ) -> Result<commands::GetAccountProfileCommand, LogicError> {
+        Ok(commands::GetAccountProfileCommand {
+            account_id: AccountId::parse(account_id)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AuthenticateRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    pub token: String,
+}
+impl AuthenticateRequest {
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::AuthenticateCommand, LogicError> {
+        Ok(commands::AuthenticateCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+            token: IdToken::parse(&self.token)?,
+        })
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreateConnectionRequest {}
 impl CreateConnectionRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::CreateConnectionCommand {
-        commands::CreateConnectionCommand {
-            connection_id: connection_id.to_string(),
-        }
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::CreateConnectionCommand, LogicError> {
+        Ok(commands::CreateConnectionCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+        })
     }
 }
 
@@ -42,31 +146,92 @@ pub struct CreateGameRequest {
     pub session_id: String,
 }
 impl CreateGameRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::CreateGameCommand {
-        commands::CreateGameCommand {
-            connection_id: connection_id.to_string(),
-            session_id: self.session_id.clone(),
-        }
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::CreateGameCommand, LogicError> {
+        Ok(commands::CreateGameCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+        })
     }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreateSessionRequest {}
 impl CreateSessionRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::CreateSessionCommand {
-        commands::CreateSessionCommand {
-            connection_id: connection_id.to_string(),
-        }
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::CreateSessionCommand, LogicError> {
+        Ok(commands::CreateSessionCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+        })
     }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DestroyConnectionRequest {}
 impl DestroyConnectionRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::DestroyConnectionCommand {
-        commands::DestroyConnectionCommand {
-            connection_id: connection_id.to_string(),
-        }
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::DestroyConnectionCommand, LogicError> {
+        Ok(commands::DestroyConnectionCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetLeaderboardRequest {
+    pub stat: String,
+    #[serde(rename = "gameId")]
+    pub game_id: Option<String>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+impl GetLeaderboardRequest {
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::GetLeaderboardCommand, LogicError> {
+        let query = self.to_query()?;
+        Ok(commands::GetLeaderboardCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            game_id: query.game_id,
+            page: query.page,
+            stat: query.stat,
+        })
+    }
+
+    pub fn to_query(&self) -> Result<commands::QueryLeaderboardCommand, LogicError> {
+        let game_id = match self.game_id {
+            Some(ref game_id) => Some(GameCode::parse(game_id)?),
+            None => None,
+        };
+        Ok(commands::QueryLeaderboardCommand {
+            game_id,
+            page: Page::parse(self.offset, self.limit)?,
+            stat: LeaderboardStat::parse(&self.stat)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetProfileRequest {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+}
+impl GetProfileRequest {
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::GetProfileCommand, LogicError> {
+        Ok(commands::GetProfileCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+        })
     }
 }
 
@@ -76,11 +241,11 @@ pub struct NewRoundRequest {
     pub session_id: String,
 }
 impl NewRoundRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::NewRoundCommand {
-        commands::NewRoundCommand {
-            connection_id: connection_id.to_string(),
-            session_id: self.session_id.clone(),
-        }
+    pub fn to_command(&self, connection_id: &str) -> Result<commands::NewRoundCommand, LogicError> {
+        Ok(commands::NewRoundCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+        })
     }
 }
 
@@ -90,11 +255,11 @@ pub struct RollDiceRequest {
     pub session_id: String,
 }
 impl RollDiceRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::RollDiceCommand {
-        commands::RollDiceCommand {
-            connection_id: connection_id.to_string(),
-            session_id: self.session_id.clone(),
-        }
+    pub fn to_command(&self, connection_id: &str) -> Result<commands::RollDiceCommand, LogicError> {
+        Ok(commands::RollDiceCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+        })
     }
 }
 
@@ -107,27 +272,40 @@ pub struct SetNicknameRequest {
     pub session_id: String,
 }
 impl SetNicknameRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::SetNicknameCommand {
-        commands::SetNicknameCommand {
-            account_id: self.account_id.clone(),
-            connection_id: connection_id.to_string(),
-            session_id: self.session_id.clone(),
-            nickname: self.nickname.clone(),
-        }
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::SetNicknameCommand, LogicError> {
+        Ok(commands::SetNicknameCommand {
+            account_id: self
+                .account_id
+                .as_deref()
+                .map(AccountId::parse)
+                .transpose()?,
+            connection_id: ConnectionId::parse(connection_id)?,
+            session_id: SessionId::parse(&self.session_id)?,
+            nickname: Nickname::parse(&self.nickname)?,
+        })
     }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SetSessionRequest {
+    #[serde(rename = "reconnectToken")]
+    pub reconnect_token: String,
     #[serde(rename = "sessionId")]
     pub session_id: String,
 }
 impl SetSessionRequest {
-    pub fn to_command(&self, connection_id: &str) -> commands::SetSessionCommand {
-        commands::SetSessionCommand {
-            connection_id: connection_id.to_string(),
-            session_id: self.session_id.clone(),
-        }
+    pub fn to_command(
+        &self,
+        connection_id: &str,
+    ) -> Result<commands::SetSessionCommand, LogicError> {
+        Ok(commands::SetSessionCommand {
+            connection_id: ConnectionId::parse(connection_id)?,
+            reconnect_token: ReconnectToken::parse(&self.reconnect_token)?,
+            session_id: SessionId::parse(&self.session_id)?,
+        })
     }
 }
 
@@ -0,0 +1,237 @@
+use domain::errors::LogicError;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// A route template such as `/v1/games/{game_id}/rounds`, where segments in
+/// braces capture path parameters.
+pub struct Route<T> {
+    pub method: &'static str,
+    pub template: &'static str,
+    pub handler: T,
+}
+
+pub struct Router<T: Copy> {
+    routes: Vec<Route<T>>,
+}
+
+/// Path and query string parameters of a matched request.
+#[derive(Debug, Default)]
+pub struct Params {
+    path: HashMap<String, String>,
+    query: HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl<T: Copy> Router<T> {
+    pub fn new(routes: Vec<Route<T>>) -> Self {
+        Router { routes }
+    }
+
+    /// Finds the route for a request. Unknown paths are not found, known
+    /// paths with the wrong method are not allowed.
+    pub fn find(
+        &self,
+        method: &str,
+        path: &str,
+        query: HashMap<String, String>,
+    ) -> Result<(T, Params), LogicError> {
+        let mut path_exists = false;
+        let mut query = Some(query);
+        for route in &self.routes {
+            let path_params = if let Some(params) = match_template(route.template, path) {
+                params
+            } else {
+                continue;
+            };
+            if !route.method.eq_ignore_ascii_case(method) {
+                path_exists = true;
+                continue;
+            }
+            let params = Params {
+                path: path_params,
+                query: query.take().unwrap_or_default(),
+            };
+            return Ok((route.handler, params));
+        }
+        if path_exists {
+            return Err(LogicError::MethodNotAllowed(format!("{method} {path}")));
+        }
+        Err(LogicError::ItemNotFound(format!(
+            "No route for {method} {path}"
+        )))
+    }
+}
+
+impl Params {
+    pub fn new(path: HashMap<String, String>, query: HashMap<String, String>) -> Self {
+        Params { path, query }
+    }
+
+    pub fn path(&self, name: &str) -> Result<String, LogicError> {
+        self.path
+            .get(name)
+            .cloned()
+            .ok_or(LogicError::RestError(format!(
+                "Missing path parameter {name}"
+            )))
+    }
+
+    pub fn query(&self, name: &str) -> Option<String> {
+        self.query.get(name).cloned()
+    }
+
+    pub fn query_parsed<V: FromStr>(&self, name: &str) -> Result<Option<V>, LogicError> {
+        match self.query.get(name) {
+            Some(value) => value
+                .parse::<V>()
+                .map(Some)
+                .map_err(|_| LogicError::RestError(format!("Invalid query parameter {name}"))),
+            None => Ok(None),
+        }
+    }
+}
+
+pub fn status_code(error: &LogicError) -> u16 {
+    match error {
+        LogicError::AuthenticationError(_) => 401,
+        LogicError::ConditionalCheckFailed(_) => 409,
+        LogicError::DeserializationError(_) => 400,
+        LogicError::ItemNotFound(_) => 404,
+        LogicError::MethodNotAllowed(_) => 405,
+        LogicError::NotAllowed => 403,
+        LogicError::RateLimited(_) => 429,
+        LogicError::RestError(_) => 400,
+        LogicError::ValidationError(_) => 422,
+        _ => 500,
+    }
+}
+
+pub fn to_response(result: Result<String, LogicError>) -> Response {
+    match result {
+        Ok(body) => Response { status: 200, body },
+        Err(e) => {
+            let field = match e {
+                LogicError::ValidationError(ref error) => Some(error.field.clone()),
+                _ => None,
+            };
+            let error_response = ErrorResponse {
+                error: e.name().to_string(),
+                message: e.to_string(),
+                field,
+            };
+            let body = serde_json::to_string(&error_response)
+                .unwrap_or_else(|_| r#"{"error":"SerializationError"}"#.to_string());
+            Response {
+                status: status_code(&e),
+                body,
+            }
+        }
+    }
+}
+
+pub fn parse_query(query: &str) -> HashMap<String, String> {
+    form_urlencoded::parse(query.trim_start_matches('?').as_bytes())
+        .into_owned()
+        .collect()
+}
+
+fn match_template(template: &str, path: &str) -> Option<HashMap<String, String>> {
+    let template_segments: Vec<&str> = template.trim_matches('/').split('/').collect();
+    let path_segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    if template_segments.len() != path_segments.len() {
+        return None;
+    }
+    let mut params = HashMap::new();
+    for (template_segment, path_segment) in template_segments.iter().zip(path_segments.iter()) {
+        let name = template_segment
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'));
+        match name {
+            Some(_) if path_segment.is_empty() => return None,
+            Some(name) => {
+                params.insert(name.to_string(), path_segment.to_string());
+            }
+            None if template_segment != path_segment => return None,
+            None => {}
+        }
+    }
+    Some(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::errors::ValidationError;
+
+    fn router() -> Router<u8> {
+        Router::new(vec![
+            Route {
+                method: "GET",
+                template: "/v1/games/{game_id}",
+                handler: 1,
+            },
+            Route {
+                method: "GET",
+                template: "/v1/games/{game_id}/rounds",
+                handler: 2,
+            },
+        ])
+    }
+
+    #[test]
+    fn captures_path_and_query_parameters() {
+        let (handler, params) = router()
+            .find(
+                "GET",
+                "/v1/games/AB12/rounds/",
+                parse_query("limit=5&name=a%20b"),
+            )
+            .unwrap();
+        assert_eq!(handler, 2);
+        assert_eq!(params.path("game_id").unwrap(), "AB12");
+        assert_eq!(params.query_parsed::<usize>("limit").unwrap(), Some(5));
+        assert_eq!(params.query("name").unwrap(), "a b");
+        assert!(params.query_parsed::<usize>("name").is_err());
+    }
+
+    #[test]
+    fn distinguishes_missing_routes_and_methods() {
+        let error = router()
+            .find("GET", "/v1/games", HashMap::new())
+            .err()
+            .unwrap();
+        assert_eq!(status_code(&error), 404);
+        let error = router()
+            .find("POST", "/v1/games/AB12", HashMap::new())
+            .err()
+            .unwrap();
+        assert_eq!(status_code(&error), 405);
+    }
+
+    #[test]
+    fn serialises_errors() {
+        let response = to_response(Err(LogicError::RestError(r#"bad "quote""#.into())));
+        assert_eq!(response.status, 400);
+        let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(body["error"], "RestError");
+        assert_eq!(body["message"], r#"[RestError] bad "quote""#);
+
+        let error = ValidationError::new("nickname", "Too short");
+        let response = to_response(Err(error.into()));
+        assert_eq!(response.status, 422);
+        assert!(response.body.contains(r#""field":"nickname""#));
+    }
+}
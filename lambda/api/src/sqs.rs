@@ -0,0 +1,97 @@
+use chrono::Utc;
+use domain::commands::{CheckSessionTimeoutCommand, ScheduledCommand};
+use domain::errors::LogicError;
+use scheduler::{IScheduler, ScheduledJob};
+use serde::Deserialize;
+use service;
+
+/// An EventBridge event delivered through SQS, keyed by its detail-type.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "detail-type", content = "detail")]
+pub enum Envelope {
+    CommandScheduled(ScheduledJob),
+    // Queued before commands were scheduled, a timeout check is all it meant
+    Disconnected(CheckSessionTimeoutCommand),
+}
+
+impl Envelope {
+    pub fn parse(body: &str) -> Result<Self, LogicError> {
+        serde_json::from_str(body)
+            .map_err(|e| LogicError::DeserializationError(format!("Invalid envelope: {e}")))
+    }
+}
+
+pub async fn invoke(body: &str) -> Result<String, LogicError> {
+    let envelope = Envelope::parse(body)?;
+    route(envelope).await
+}
+
+async fn route(envelope: Envelope) -> Result<String, LogicError> {
+    match envelope {
+        Envelope::CommandScheduled(job) => {
+            // The queue delay is fixed, so go round again until the job is due
+            if !job.is_due(Utc::now()) {
+                scheduler::get().await.schedule(&job).await?;
+                return Ok("Rescheduled".to_string());
+            }
+            service::run_scheduled_command::handler(&job.command).await
+        }
+        Envelope::Disconnected(command) => {
+            let command = ScheduledCommand::CheckSessionTimeout(command);
+            service::run_scheduled_command::handler(&command).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_scheduled_command() -> Result<(), LogicError> {
+        let body = json!({
+            "version": "0",
+            "source": "RustLambda-Dev.Scheduler",
+            "detail-type": "CommandScheduled",
+            "detail": {
+                "command": {"type": "CheckSessionTimeout", "session_id": "abc"},
+                "run_at": "2024-01-01T00:00:00Z",
+            },
+        });
+        let envelope = Envelope::parse(&body.to_string())?;
+        let Envelope::CommandScheduled(job) = envelope else {
+            panic!("Unexpected envelope: {envelope:?}");
+        };
+        let command = CheckSessionTimeoutCommand {
+            session_id: "abc".to_string(),
+        };
+        assert_eq!(job.command, ScheduledCommand::CheckSessionTimeout(command));
+        assert!(job.is_due(Utc::now()));
+        Ok(())
+    }
+
+    #[test]
+    fn parses_disconnected_event() -> Result<(), LogicError> {
+        let body = json!({
+            "source": "RustLambda-Dev.Websocket",
+            "detail-type": "Disconnected",
+            "detail": {"session_id": "abc"},
+        });
+        let envelope = Envelope::parse(&body.to_string())?;
+        assert!(matches!(envelope, Envelope::Disconnected(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_detail_type() {
+        let body = json!({"detail-type": "Unknown", "detail": {}});
+        assert!(Envelope::parse(&body.to_string()).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_detail() {
+        let body = json!({"detail-type": "CommandScheduled", "detail": {"session_id": "abc"}});
+        assert!(Envelope::parse(&body.to_string()).is_err());
+    }
+}
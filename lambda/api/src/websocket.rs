@@ -1,18 +1,61 @@
-use crate::requests;
+use crate::{command_queue, rate_limit, requests};
 use domain::errors::LogicError;
+use domain::values::ConnectionId;
+use metrics::names::REQUEST_COUNT;
+use metrics::IMetrics;
 use service;
+use storage::session_table::SessionItem;
+use storage::websocket_table::WebsocketItem;
 
 pub enum RequestType {
+    Authenticate(requests::AuthenticateRequest),
     Connect(requests::CreateConnectionRequest),
     CreateGame(requests::CreateGameRequest),
     CreateSession(requests::CreateSessionRequest),
     Disconnect(requests::DestroyConnectionRequest),
+    GetLeaderboard(requests::GetLeaderboardRequest),
+    GetProfile(requests::GetProfileRequest),
     NewRound(requests::NewRoundRequest),
     RollDice(requests::RollDiceRequest),
     SetNickname(requests::SetNicknameRequest),
     SetSession(requests::SetSessionRequest),
 }
 
+impl RequestType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RequestType::Authenticate(_) => "Authenticate",
+            RequestType::Connect(_) => "Connect",
+            RequestType::CreateGame(_) => "CreateGame",
+            RequestType::CreateSession(_) => "CreateSession",
+            RequestType::Disconnect(_) => "Disconnect",
+            RequestType::GetLeaderboard(_) => "GetLeaderboard",
+            RequestType::GetProfile(_) => "GetProfile",
+            RequestType::NewRound(_) => "NewRound",
+            RequestType::RollDice(_) => "RollDice",
+            RequestType::SetNickname(_) => "SetNickname",
+            RequestType::SetSession(_) => "SetSession",
+        }
+    }
+
+    /// The action clients send, for requests they can send.
+    fn action(&self) -> Option<&'static str> {
+        match self {
+            RequestType::Authenticate(_) => Some("authenticate"),
+            RequestType::Connect(_) => None,
+            RequestType::CreateGame(_) => Some("createGame"),
+            RequestType::CreateSession(_) => Some("getSession"),
+            RequestType::Disconnect(_) => None,
+            RequestType::GetLeaderboard(_) => Some("getLeaderboard"),
+            RequestType::GetProfile(_) => Some("getProfile"),
+            RequestType::NewRound(_) => Some("newRound"),
+            RequestType::RollDice(_) => Some("rollDice"),
+            RequestType::SetNickname(_) => Some("setNickname"),
+            RequestType::SetSession(_) => Some("setSession"),
+        }
+    }
+}
+
 pub fn get_request_type(route_key: &str, body_str: &str) -> Result<RequestType, LogicError> {
     if route_key == "$connect" {
         return Ok(RequestType::Connect(requests::CreateConnectionRequest {}));
@@ -23,16 +66,31 @@ pub fn get_request_type(route_key: &str, body_str: &str) -> Result<RequestType,
         ));
     }
 
-    let request: requests::WebsocketRequest = serde_json::from_str(&body_str)
+    let request: requests::WebsocketRequest = serde_json::from_str(body_str)
         .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
     println!("Request action {}", request.action);
     println!("Request data {}", request.data);
     match request.action.as_str() {
+        "authenticate" => {
+            let request: requests::AuthenticateRequest = serde_json::from_value(request.data)
+                .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
+            Ok(RequestType::Authenticate(request))
+        }
         "createGame" => {
             let request: requests::CreateGameRequest = serde_json::from_value(request.data)
                 .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
             Ok(RequestType::CreateGame(request))
         }
+        "getLeaderboard" => {
+            let request: requests::GetLeaderboardRequest = serde_json::from_value(request.data)
+                .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
+            Ok(RequestType::GetLeaderboard(request))
+        }
+        "getProfile" => {
+            let request: requests::GetProfileRequest = serde_json::from_value(request.data)
+                .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
+            Ok(RequestType::GetProfile(request))
+        }
         "getSession" => {
             let request: requests::CreateSessionRequest = serde_json::from_value(request.data)
                 .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
@@ -62,39 +120,248 @@ pub fn get_request_type(route_key: &str, body_str: &str) -> Result<RequestType,
     }
 }
 
+/// Requests from one connection, or for one game, are handled one at a time
+/// in the order they arrived.
 pub async fn route(request_type: &RequestType, connection_id: &str) -> Result<String, LogicError> {
+    let result = match limit(request_type, connection_id).await {
+        Ok(()) => {
+            let keys = queue_keys(connection_id).await;
+            command_queue::get()
+                .run(&keys, route_to_handler(request_type, connection_id))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    let outcome = match &result {
+        Ok(_) => "Ok",
+        Err(e) => e.name(),
+    };
+    let metrics = metrics::get().await;
+    metrics.increment(
+        REQUEST_COUNT,
+        &[("RequestType", request_type.name()), ("Outcome", outcome)],
+    );
+    result
+}
+
+async fn limit(request_type: &RequestType, connection_id: &str) -> Result<(), LogicError> {
+    match request_type.action() {
+        Some(action) => rate_limit::enforce(action, connection_id).await,
+        None => Ok(()),
+    }
+}
+
+/// The connection, and the game its bound session is in.
+pub async fn queue_keys(connection_id: &str) -> Vec<String> {
+    let mut keys = vec![format!("connection:{connection_id}")];
+    let db = storage::get().await;
+    // Anything that can't be read fails in the handler, with a proper error
+    let Ok(WebsocketItem {
+        session_id: Some(session_id),
+        ..
+    }) = WebsocketItem::from_db(connection_id, &db).await
+    else {
+        return keys;
+    };
+    if let Ok(SessionItem {
+        game_id: Some(game_id),
+        ..
+    }) = SessionItem::from_db(&session_id, &db).await
+    {
+        keys.push(format!("game:{game_id}"));
+    }
+    keys
+}
+
+async fn route_to_handler(
+    request_type: &RequestType,
+    connection_id: &str,
+) -> Result<String, LogicError> {
     match request_type {
+        RequestType::Authenticate(request) => {
+            let command = request.to_command(connection_id)?;
+            service::authenticate::handler(&command).await
+        }
         RequestType::Connect(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::create_connection::handler(&command).await
         }
         RequestType::CreateGame(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::create_game::handler(&command).await
         }
         RequestType::CreateSession(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::create_session::handler(&command).await
         }
         RequestType::Disconnect(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::destroy_connection::handler(&command).await
         }
+        RequestType::GetLeaderboard(request) => {
+            let command = request.to_command(connection_id)?;
+            service::get_leaderboard::handler(&command).await
+        }
+        RequestType::GetProfile(request) => {
+            let command = request.to_command(connection_id)?;
+            service::get_profile::handler(&command).await
+        }
         RequestType::NewRound(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::new_round::handler(&command).await
         }
         RequestType::RollDice(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::roll_dice::handler(&command).await
         }
-        RequestType::SetNickname(request) => {
-            let command = request.to_command(connection_id);
-            service::set_nickname::handler(&command).await
-        }
+        RequestType::SetNickname(request) => match request.to_command(connection_id) {
+            Ok(command) => service::set_nickname::handler(&command).await,
+            Err(LogicError::ValidationError(e)) if e.field == "nickname" => {
+                let connection_id = ConnectionId::parse(connection_id)?;
+                service::set_nickname::reject(&connection_id, &e).await
+            }
+            Err(e) => Err(e),
+        },
         RequestType::SetSession(request) => {
-            let command = request.to_command(connection_id);
+            let command = request.to_command(connection_id)?;
             service::set_session::handler(&command).await
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notifier::INotifier;
+    use std::env;
+    use std::sync::Mutex;
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    fn setup() {
+        env::set_var("WEBSOCKET_TABLE_NAME", "WEBSOCKET");
+        env::set_var("GAME_TABLE_NAME", "GAME");
+        env::set_var("LEADERBOARD_TABLE_NAME", "LEADERBOARD");
+        env::set_var("PROFILE_TABLE_NAME", "PROFILE");
+        env::set_var("OUTBOX_TABLE_NAME", "OUTBOX");
+        env::set_var("RATE_LIMIT_TABLE_NAME", "RATE_LIMIT");
+    }
+
+    #[tokio::test]
+    async fn waits_for_earlier_requests_from_the_connection() -> Result<(), LogicError> {
+        setup();
+        let connection_id = Uuid::new_v4().to_string();
+        let connect = RequestType::Connect(requests::CreateConnectionRequest {});
+        route(&connect, &connection_id).await?;
+
+        let order = Mutex::new(vec![]);
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        let keys = vec![format!("connection:{connection_id}")];
+        // Joined futures are first polled in order, so the earlier command
+        // holds the connection before the later request reaches the queue
+        let earlier = command_queue::get().run(&keys, async {
+            receiver.await.unwrap();
+            order.lock().unwrap().push("earlier");
+        });
+        let later = async {
+            let request = RequestType::CreateSession(requests::CreateSessionRequest {});
+            let result = route(&request, &connection_id).await;
+            order.lock().unwrap().push("later");
+            result
+        };
+        let release = async { sender.send(()).unwrap() };
+
+        let (_, result, _) = tokio::join!(earlier, later, release);
+
+        result?;
+        assert_eq!(*order.lock().unwrap(), vec!["earlier", "later"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn waits_for_earlier_requests_for_the_game() -> Result<(), LogicError> {
+        setup();
+        let db = storage::get().await;
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let game_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.game_id = Some(game_id.clone());
+        db.write(vec![connection.save()?, session.save()?]).await?;
+        let keys = queue_keys(&connection_id).await;
+        assert_eq!(
+            keys,
+            vec![
+                format!("connection:{connection_id}"),
+                format!("game:{game_id}")
+            ]
+        );
+
+        let order = Mutex::new(vec![]);
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        // Another player's command holds the game
+        let game_keys = vec![format!("game:{game_id}")];
+        let earlier = command_queue::get().run(&game_keys, async {
+            receiver.await.unwrap();
+            order.lock().unwrap().push("earlier");
+        });
+        let later = async {
+            let request = RequestType::RollDice(requests::RollDiceRequest {
+                session_id: session_id.clone(),
+            });
+            let _ = route(&request, &connection_id).await;
+            order.lock().unwrap().push("later");
+        };
+        let release = async { sender.send(()).unwrap() };
+
+        tokio::join!(earlier, later, release);
+
+        assert_eq!(*order.lock().unwrap(), vec!["earlier", "later"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tells_client_when_rate_limited() -> Result<(), LogicError> {
+        setup();
+        let connection_id = Uuid::new_v4().to_string();
+        let request = RequestType::CreateGame(requests::CreateGameRequest {
+            session_id: Uuid::new_v4().to_string(),
+        });
+        let limit = config::get().rate_limits.for_action("createGame");
+        for _ in 0..limit.requests {
+            // The session doesn't exist, but the attempt still counts
+            let result = route(&request, &connection_id).await;
+            assert!(!matches!(result, Err(LogicError::RateLimited(_))));
+        }
+
+        let result = route(&request, &connection_id).await;
+
+        assert!(matches!(result, Err(LogicError::RateLimited(_))));
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_id);
+        let message: serde_json::Value = serde_json::from_str(messages.last().unwrap()).unwrap();
+        assert_eq!(message["action"], "rateLimited");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn only_charges_the_session_bound_to_the_connection() -> Result<(), LogicError> {
+        setup();
+        let request = RequestType::CreateGame(requests::CreateGameRequest {
+            session_id: Uuid::new_v4().to_string(),
+        });
+        let limit = config::get().rate_limits.for_action("createGame");
+        let connection_id = Uuid::new_v4().to_string();
+        for _ in 0..=limit.requests {
+            let _ = route(&request, &connection_id).await;
+        }
+
+        // Another connection naming the same session has its own bucket
+        let other_connection_id = Uuid::new_v4().to_string();
+        let result = route(&request, &other_connection_id).await;
+
+        assert!(!matches!(result, Err(LogicError::RateLimited(_))));
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ mod websocket;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
+    config::install_from_args();
     lambda_http::tracing::init_default_subscriber();
     lambda_http::run(lambda_http::service_fn(invoke)).await
 }
@@ -14,9 +15,10 @@ pub async fn invoke(event: Request) -> Result<Response<Body>, Error> {
     let ctx_str = serde_json::to_string(&ctx)?;
     println!("ctx_str: {ctx_str}");
     let body = event.body();
+    let query = event.query_string_parameters();
     match ctx {
-        RequestContext::ApiGatewayV1(ctx) => api::invoke(&body, &ctx).await,
-        RequestContext::WebSocket(ctx) => websocket::invoke(&body, &ctx).await,
+        RequestContext::ApiGatewayV1(ctx) => api::invoke(body, &query, &ctx).await,
+        RequestContext::WebSocket(ctx) => websocket::invoke(body, &ctx).await,
         _ => Err(Error::from("Invalid request context")),
     }
 }
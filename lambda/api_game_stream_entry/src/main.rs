@@ -0,0 +1,80 @@
+use aws_lambda_events::dynamodb::{Event, EventRecord};
+use aws_lambda_events::streams::{DynamoDbBatchItemFailure, DynamoDbEventResponse};
+use aws_sdk_dynamodb::types::AttributeValue;
+use lambda_runtime::{service_fn, Error, LambdaEvent};
+use std::collections::HashMap;
+use storage::stream_record::StreamRecord;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    config::install_from_args();
+    lambda_runtime::run(service_fn(handler)).await
+}
+
+/// Sends each committed change to the game's players. Only the first
+/// failure is reported, the stream retries from there so players never see
+/// an older state after a newer one.
+async fn handler(event: LambdaEvent<Event>) -> Result<DynamoDbEventResponse, Error> {
+    let mut response = DynamoDbEventResponse {
+        batch_item_failures: vec![],
+    };
+    for record in event.payload.records {
+        match service::game_stream::handler(&to_stream_record(&record)).await {
+            Ok(message) => println!("Handled {}: {message}", record.event_id),
+            Err(e) => {
+                println!("Failed {}: {e}", record.event_id);
+                response.batch_item_failures.push(DynamoDbBatchItemFailure {
+                    item_identifier: record.change.sequence_number.clone(),
+                });
+                break;
+            }
+        }
+    }
+    Ok(response)
+}
+
+fn to_stream_record(record: &EventRecord) -> StreamRecord {
+    StreamRecord {
+        old_image: to_image(&record.change.old_image),
+        new_image: to_image(&record.change.new_image),
+    }
+}
+
+// Missing images are deserialised as empty items
+fn to_image(item: &serde_dynamo::Item) -> Option<HashMap<String, AttributeValue>> {
+    if item.is_empty() {
+        return None;
+    }
+    Some(item.clone().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_images_from_stream_record() {
+        let record = json!({
+            "awsRegion": "ap-southeast-2",
+            "eventID": "1",
+            "eventName": "REMOVE",
+            "eventSource": "aws:dynamodb",
+            "dynamodb": {
+                "ApproximateCreationDateTime": 1700000000.0,
+                "Keys": {"id": {"S": "ABCD"}},
+                "OldImage": {"id": {"S": "ABCD"}, "version": {"N": "3"}},
+                "SequenceNumber": "100",
+                "SizeBytes": 20,
+                "StreamViewType": "NEW_AND_OLD_IMAGES",
+            },
+        });
+        let record: EventRecord = serde_json::from_value(record).unwrap();
+
+        let stream_record = to_stream_record(&record);
+        assert!(stream_record.new_image.is_none());
+        let old_image = stream_record.old_image.unwrap();
+        assert_eq!(old_image["id"], AttributeValue::S("ABCD".to_string()));
+        assert_eq!(old_image["version"], AttributeValue::N("3".to_string()));
+    }
+}
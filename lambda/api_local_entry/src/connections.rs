@@ -0,0 +1,102 @@
+use actix::{Message, Recipient};
+use actix_web::{web, HttpResponse};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// A message pushed by the server to a websocket client.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Push(pub String);
+
+/// Asks a websocket actor to close its connection.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Close;
+
+/// The open websocket actors, keyed by connection ID. This plays the part of
+/// API Gateway, which owns the sockets in the cloud.
+#[derive(Default)]
+pub struct ConnectionRegistry {
+    connections: RwLock<HashMap<String, Connection>>,
+}
+
+struct Connection {
+    push: Recipient<Push>,
+    close: Recipient<Close>,
+}
+
+impl ConnectionRegistry {
+    pub fn register(&self, connection_id: &str, push: Recipient<Push>, close: Recipient<Close>) {
+        let mut connections = self.connections.write().unwrap();
+        connections.insert(connection_id.to_string(), Connection { push, close });
+    }
+
+    pub fn unregister(&self, connection_id: &str) {
+        let mut connections = self.connections.write().unwrap();
+        connections.remove(connection_id);
+    }
+
+    pub fn is_connected(&self, connection_id: &str) -> bool {
+        let connections = self.connections.read().unwrap();
+        connections.contains_key(connection_id)
+    }
+
+    /// Returns false if the connection is gone.
+    pub fn push(&self, connection_id: &str, message: String) -> bool {
+        let connections = self.connections.read().unwrap();
+        match connections.get(connection_id) {
+            Some(connection) => connection.push.try_send(Push(message)).is_ok(),
+            None => false,
+        }
+    }
+
+    pub fn close(&self, connection_id: &str) -> bool {
+        let connections = self.connections.read().unwrap();
+        match connections.get(connection_id) {
+            Some(connection) => connection.close.try_send(Close).is_ok(),
+            None => false,
+        }
+    }
+}
+
+// The API Gateway Management API, so the cloud notifier works unchanged.
+// API Gateway answers 410 for connections that have gone.
+
+pub async fn post_to_connection(
+    path: web::Path<String>,
+    body: web::Bytes,
+    registry: web::Data<ConnectionRegistry>,
+) -> HttpResponse {
+    let connection_id = path.into_inner();
+    let message = String::from_utf8_lossy(&body).to_string();
+    match registry.push(&connection_id, message) {
+        true => HttpResponse::Ok().finish(),
+        false => gone(),
+    }
+}
+
+pub async fn get_connection(
+    path: web::Path<String>,
+    registry: web::Data<ConnectionRegistry>,
+) -> HttpResponse {
+    match registry.is_connected(&path.into_inner()) {
+        true => HttpResponse::Ok().json(serde_json::json!({})),
+        false => gone(),
+    }
+}
+
+pub async fn delete_connection(
+    path: web::Path<String>,
+    registry: web::Data<ConnectionRegistry>,
+) -> HttpResponse {
+    match registry.close(&path.into_inner()) {
+        true => HttpResponse::NoContent().finish(),
+        false => gone(),
+    }
+}
+
+fn gone() -> HttpResponse {
+    HttpResponse::Gone()
+        .content_type("application/json")
+        .body(r#"{"message":"GoneException"}"#)
+}
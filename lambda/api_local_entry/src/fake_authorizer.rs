@@ -0,0 +1,44 @@
+use actix_web::HttpRequest;
+use serde_json::{json, Value};
+
+const SUB_HEADER: &str = "x-fake-sub";
+
+/// Stands in for the API Gateway Cognito authorizer, producing the same
+/// claims shape. Every request is the configured account unless it sends an
+/// `X-Fake-Sub` header, so several players can be simulated at once.
+pub struct FakeAuthorizer {
+    sub: String,
+    email: String,
+    username: String,
+    require_header: bool,
+}
+
+impl FakeAuthorizer {
+    pub fn from_config() -> Self {
+        let config = config::get();
+        FakeAuthorizer {
+            sub: config.local_auth_sub.clone(),
+            email: config.local_auth_email.clone(),
+            username: config.local_auth_username.clone(),
+            require_header: config.local_auth_require_header,
+        }
+    }
+
+    /// Returns None when the request would have been rejected by Cognito.
+    pub fn claims(&self, req: &HttpRequest) -> Option<Value> {
+        let header = req
+            .headers()
+            .get(SUB_HEADER)
+            .and_then(|value| value.to_str().ok());
+        let (sub, username) = match header {
+            Some(sub) => (sub.to_string(), sub.to_string()),
+            None if self.require_header => return None,
+            None => (self.sub.clone(), self.username.clone()),
+        };
+        Some(json!({
+            "sub": sub,
+            "email": self.email,
+            "cognito:username": username,
+        }))
+    }
+}
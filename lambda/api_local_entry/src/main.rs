@@ -1,47 +1,350 @@
-use actix::{Actor, StreamHandler};
+use actix::{Actor, ActorContext, AsyncContext, Handler, StreamHandler};
+use actix_http::ws::Item;
 use actix_web::{web, App, Error, HttpRequest, HttpResponse, HttpServer};
-use actix_web_actors::ws;
+use actix_web_actors::ws::{self, CloseCode, CloseReason};
 use api::{
-    requests,
+    api::{self as rest, Caller},
+    requests, router,
     websocket::{self, RequestType},
 };
+use config::{Config, Settings};
+use connections::{Close, ConnectionRegistry, Push};
 use domain::errors::LogicError;
-use std::env;
+use fake_authorizer::FakeAuthorizer;
+#[cfg(feature = "in_memory")]
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use uuid::Uuid;
+mod connections;
+mod fake_authorizer;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+// API Gateway drops connections idle for 10 minutes, be stricter locally
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(30);
+// API Gateway limits websocket frames to 32KB and messages to 128KB
+const MAX_FRAME_SIZE: usize = 32 * 1024;
+const MAX_MESSAGE_SIZE: usize = 128 * 1024;
+#[cfg(feature = "in_memory")]
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+#[cfg(feature = "in_memory")]
+const RELAY_INTERVAL: Duration = Duration::from_millis(100);
 
 struct MyWs {
     connection_id: String,
+    registry: web::Data<ConnectionRegistry>,
+    last_heartbeat: Instant,
+    // Messages are handled one at a time, in the order they arrived
+    messages: Option<UnboundedSender<String>>,
+    // A message split over continuation frames
+    fragments: Option<Vec<u8>>,
+}
+
+impl MyWs {
+    fn new(connection_id: &str, registry: web::Data<ConnectionRegistry>) -> Self {
+        MyWs {
+            connection_id: connection_id.to_string(),
+            registry,
+            last_heartbeat: Instant::now(),
+            messages: None,
+            fragments: None,
+        }
+    }
+
+    fn heartbeat(&self, ctx: &mut <Self as Actor>::Context) {
+        ctx.run_interval(HEARTBEAT_INTERVAL, |actor, ctx| {
+            if Instant::now().duration_since(actor.last_heartbeat) > CLIENT_TIMEOUT {
+                println!(
+                    "Heartbeat timed out, connection_id: {}",
+                    actor.connection_id
+                );
+                ctx.stop();
+                return;
+            }
+            ctx.ping(b"");
+        });
+    }
+
+    fn enqueue(&mut self, bytes: Vec<u8>, ctx: &mut <Self as Actor>::Context) {
+        let Ok(text) = String::from_utf8(bytes) else {
+            close(ctx, CloseCode::Invalid, "Messages must be UTF-8 text");
+            return;
+        };
+        if let Some(messages) = &self.messages {
+            let _ = messages.send(text);
+        }
+    }
+
+    fn continuation(&mut self, item: Item, ctx: &mut <Self as Actor>::Context) {
+        let (bytes, last) = match item {
+            Item::FirstText(bytes) | Item::FirstBinary(bytes) => {
+                self.fragments = Some(vec![]);
+                (bytes, false)
+            }
+            Item::Continue(bytes) => (bytes, false),
+            Item::Last(bytes) => (bytes, true),
+        };
+        let Some(fragments) = self.fragments.as_mut() else {
+            close(
+                ctx,
+                CloseCode::Protocol,
+                "Continuation without a first frame",
+            );
+            return;
+        };
+        fragments.extend_from_slice(&bytes);
+        if fragments.len() > MAX_MESSAGE_SIZE {
+            close(ctx, CloseCode::Size, "Message too large");
+            return;
+        }
+        if last {
+            let message = self.fragments.take().unwrap_or_default();
+            self.enqueue(message, ctx);
+        }
+    }
 }
 
 impl Actor for MyWs {
     type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let address = ctx.address();
+        self.registry.register(
+            &self.connection_id,
+            address.clone().recipient(),
+            address.recipient(),
+        );
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.messages = Some(sender);
+        process_messages(&self.connection_id, receiver);
+        self.heartbeat(ctx);
+    }
+
+    // However the socket ended, the queue drains then the connection is
+    // destroyed, just as API Gateway always runs $disconnect
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        println!("stopped, connection_id: {}", self.connection_id);
+        self.registry.unregister(&self.connection_id);
+        self.messages.take();
+    }
+}
+
+impl Handler<Push> for MyWs {
+    type Result = ();
+
+    fn handle(&mut self, msg: Push, ctx: &mut Self::Context) {
+        ctx.text(msg.0);
+    }
+}
+
+impl Handler<Close> for MyWs {
+    type Result = ();
+
+    fn handle(&mut self, _msg: Close, ctx: &mut Self::Context) {
+        close(ctx, CloseCode::Normal, "Connection deleted");
+    }
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    env::set_var("WEBSOCKET_TABLE_NAME", "RustLambda-DevWebsocket");
-    env::set_var("GAME_TABLE_NAME", "RustLambda-DevGame");
-    env::set_var("AWS_REGION", "eu-west-2");
-    env::set_var("API_GATEWAY_URL", "ws://127.0.0.1:8080/ws/");
-    HttpServer::new(|| App::new().route("/ws/", web::get().to(index)))
-        .bind(("127.0.0.1", 8080))?
-        .run()
+    let config = install_config();
+    let authorizer = web::Data::new(FakeAuthorizer::from_config());
+    let registry = web::Data::new(ConnectionRegistry::default());
+    forward_notifications(&registry).await;
+    sweep_periodically();
+    relay_outbox_periodically();
+    broadcast_game_changes().await;
+    run_scheduled_commands().await;
+    HttpServer::new(move || {
+        App::new()
+            .app_data(authorizer.clone())
+            .app_data(registry.clone())
+            .route("/ws/", web::get().to(index))
+            .service(
+                web::resource("/ws/@connections/{connection_id}")
+                    .route(web::post().to(connections::post_to_connection))
+                    .route(web::get().to(connections::get_connection))
+                    .route(web::delete().to(connections::delete_connection)),
+            )
+            .default_service(web::to(rest_api))
+    })
+    .bind(config.bind_address)?
+    .run()
+    .await
+}
+
+fn install_config() -> &'static Config {
+    let mut sources = config::read_sources(local_defaults());
+    // The cloud notifier posts to {api_gateway_url}/@connections/{id}
+    if let Ok(sources) = sources.as_mut() {
+        let bind_address = sources.settings.get("bind_address").unwrap_or_default();
+        let gateway_url = format!("http://{bind_address}/ws");
+        sources
+            .settings
+            .set_default("api_gateway_url", &gateway_url);
+    }
+    config::install(sources)
+}
+
+#[cfg(not(feature = "in_memory"))]
+fn local_defaults() -> Settings {
+    Settings::new()
+        .with("aws_region", "eu-west-2")
+        .with("game_table_name", "RustLambda-DevGame")
+        .with("leaderboard_table_name", "RustLambda-DevLeaderboard")
+        .with("name_table_name", "RustLambda-Dev")
+        .with("profile_table_name", "RustLambda-DevProfile")
+        .with("outbox_table_name", "RustLambda-DevOutbox")
+        .with("rate_limit_table_name", "RustLambda-DevRateLimit")
+        .with("websocket_table_name", "RustLambda-DevWebsocket")
+}
+
+// The in memory client only knows its own table names
+#[cfg(feature = "in_memory")]
+fn local_defaults() -> Settings {
+    Settings::new()
+        .with("game_table_name", "GAME")
+        .with("leaderboard_table_name", "LEADERBOARD")
+        .with("profile_table_name", "PROFILE")
+        .with("outbox_table_name", "OUTBOX")
+        .with("rate_limit_table_name", "RATE_LIMIT")
+        .with("websocket_table_name", "WEBSOCKET")
+}
+
+// The in memory notifier never calls the management API, so pass its
+// messages straight to the sockets
+#[cfg(feature = "in_memory")]
+async fn forward_notifications(registry: &web::Data<ConnectionRegistry>) {
+    let registry = registry.clone();
+    notifier::get()
+        .await
+        .forward_to(Box::new(move |connection_id, message| {
+            registry.push(connection_id, message.to_string())
+        }));
+}
+
+#[cfg(not(feature = "in_memory"))]
+async fn forward_notifications(_registry: &web::Data<ConnectionRegistry>) {}
+
+// In memory state only lives in this process, so api_sweep_entry can't reach
+// it, sweep here instead
+#[cfg(feature = "in_memory")]
+fn sweep_periodically() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        loop {
+            interval.tick().await;
+            match service::sweep::handler().await {
+                Ok(summary) => println!("Swept: {summary}"),
+                Err(e) => println!("Sweep failed: {e}"),
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "in_memory"))]
+fn sweep_periodically() {}
+
+// There's no table stream in memory, so poll the outbox instead
+#[cfg(feature = "in_memory")]
+fn relay_outbox_periodically() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(RELAY_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = service::relay_outbox::handler().await {
+                println!("Relay failed: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "in_memory"))]
+fn relay_outbox_periodically() {}
+
+// Stands in for the game table's stream. Changes are handled one at a time,
+// so players see them in the order they were written
+#[cfg(feature = "in_memory")]
+async fn broadcast_game_changes() {
+    let game_table_name = config::get().game_table_name.clone();
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    storage::get()
         .await
+        .stream_to(Box::new(move |table_name, record| {
+            if table_name == game_table_name {
+                let _ = sender.send(record);
+            }
+        }));
+    tokio::spawn(async move {
+        while let Some(record) = receiver.recv().await {
+            if let Err(e) = service::game_stream::handler(&record).await {
+                println!("Game stream failed: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(not(feature = "in_memory"))]
+async fn broadcast_game_changes() {}
+
+// Without in_memory, scheduled commands go to AWS and run there
+#[cfg(feature = "in_memory")]
+async fn run_scheduled_commands() {
+    scheduler::get().await.run_with(Arc::new(|command| {
+        Box::pin(async move {
+            match service::run_scheduled_command::handler(&command).await {
+                Ok(message) => println!("Ran {command:?}: {message}"),
+                Err(e) => println!("Failed {command:?}: {e}"),
+            }
+        })
+    }));
+}
+
+#[cfg(not(feature = "in_memory"))]
+async fn run_scheduled_commands() {}
+
+async fn rest_api(
+    req: HttpRequest,
+    body: web::Bytes,
+    authorizer: web::Data<FakeAuthorizer>,
+) -> HttpResponse {
+    println!("{} {}", req.method(), req.path());
+    let response = match authorizer.claims(&req) {
+        Some(claims) => match Caller::from_claims(&claims) {
+            Ok(caller) => {
+                let query = router::parse_query(req.query_string());
+                rest::handle(req.method().as_str(), req.path(), query, &body, &caller).await
+            }
+            Err(e) => router::to_response(Err(e)),
+        },
+        None => router::to_response(Err(LogicError::AuthenticationError(
+            "Unauthorized".to_string(),
+        ))),
+    };
+    let status = actix_web::http::StatusCode::from_u16(response.status)
+        .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR);
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(response.body)
 }
 
-async fn index(req: HttpRequest, stream: web::Payload) -> Result<HttpResponse, Error> {
+async fn index(
+    req: HttpRequest,
+    stream: web::Payload,
+    registry: web::Data<ConnectionRegistry>,
+) -> Result<HttpResponse, Error> {
     println!("index");
     let connection_id = Uuid::new_v4().to_string();
-    let websocket = MyWs {
-        connection_id: connection_id.clone(),
-    };
-    let resp = ws::start(websocket, &req, stream);
+    let websocket = MyWs::new(&connection_id, registry);
+    let resp = ws::WsResponseBuilder::new(websocket, &req, stream)
+        .frame_size(MAX_FRAME_SIZE)
+        .start();
     let result = connect(&connection_id).await;
     match result {
         Ok(_) => resp,
         Err(e) => {
             let message = format!("Error: {e}");
-            return Err(actix_web::error::ErrorInternalServerError(message));
+            Err(actix_web::error::ErrorInternalServerError(message))
         }
     }
 }
@@ -49,41 +352,53 @@ async fn index(req: HttpRequest, stream: web::Payload) -> Result<HttpResponse, E
 impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MyWs {
     fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
         println!("handle, connection_id: {}", self.connection_id);
-        match msg {
-            Ok(ws::Message::Text(text)) => {
-                println!("text");
-                let connection_id = self.connection_id.clone();
-                actix::spawn(async move {
-                    let result = message(&connection_id, &text).await;
-                    match result {
-                        Ok(_) => (),
-                        Err(e) => println!("Error disconnecting: {e}"),
-                    }
-                });
-                // ctx.spawn(wrap_future(fut.into_actor(self)));
-
-                // ctx.text(text)
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(e) => {
+                println!("Protocol error: {e}");
+                close(ctx, CloseCode::Protocol, &e.to_string());
+                return;
             }
-            Ok(ws::Message::Close(reason)) => {
+        };
+        self.last_heartbeat = Instant::now();
+        match msg {
+            ws::Message::Text(text) => self.enqueue(text.into_bytes().to_vec(), ctx),
+            ws::Message::Binary(bytes) => self.enqueue(bytes.to_vec(), ctx),
+            ws::Message::Continuation(item) => self.continuation(item, ctx),
+            ws::Message::Ping(bytes) => ctx.pong(&bytes),
+            ws::Message::Pong(_) => (),
+            ws::Message::Close(reason) => {
                 println!("close");
-                let connection_id = self.connection_id.clone();
-                actix::spawn(async move {
-                    let result = disconnect(&connection_id).await;
-                    match result {
-                        Ok(_) => (),
-                        Err(e) => println!("Error disconnecting: {e}"),
-                    }
-                });
-                ctx.close(reason)
-            }
-            _ => {
-                println!("Recieved unsupported message type");
-                ()
+                ctx.close(reason);
+                ctx.stop();
             }
+            ws::Message::Nop => (),
         }
     }
 }
 
+fn close(ctx: &mut <MyWs as Actor>::Context, code: CloseCode, description: &str) {
+    ctx.close(Some(CloseReason {
+        code,
+        description: Some(description.to_string()),
+    }));
+    ctx.stop();
+}
+
+fn process_messages(connection_id: &str, mut messages: UnboundedReceiver<String>) {
+    let connection_id = connection_id.to_string();
+    actix::spawn(async move {
+        while let Some(text) = messages.recv().await {
+            if let Err(e) = message(&connection_id, &text).await {
+                println!("Error handling message: {e}");
+            }
+        }
+        if let Err(e) = disconnect(&connection_id).await {
+            println!("Error disconnecting: {e}");
+        }
+    });
+}
+
 async fn connect(connection_id: &str) -> Result<String, LogicError> {
     let request_type = RequestType::Connect(requests::CreateConnectionRequest {});
     let result = websocket::route(&request_type, connection_id).await;
@@ -92,12 +407,12 @@ async fn connect(connection_id: &str) -> Result<String, LogicError> {
 
 async fn disconnect(connection_id: &str) -> Result<String, LogicError> {
     let request_type = RequestType::Disconnect(requests::DestroyConnectionRequest {});
-    let result = websocket::route(&request_type, &connection_id).await;
+    let result = websocket::route(&request_type, connection_id).await;
     result
 }
 
 async fn message(connection_id: &str, text: &str) -> Result<String, LogicError> {
-    let request_type = websocket::get_request_type("$default", &text)?;
-    let result = websocket::route(&request_type, &connection_id).await;
+    let request_type = websocket::get_request_type("$default", text)?;
+    let result = websocket::route(&request_type, connection_id).await;
     result
 }
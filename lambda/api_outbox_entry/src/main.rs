@@ -0,0 +1,42 @@
+use aws_lambda_events::dynamodb::{Event, EventRecord};
+use aws_lambda_events::streams::{DynamoDbBatchItemFailure, DynamoDbEventResponse};
+use aws_sdk_dynamodb::types::AttributeValue;
+use lambda_runtime::{service_fn, Error, LambdaEvent};
+use std::collections::HashMap;
+use storage::outbox_table::OutboxItem;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    config::install_from_args();
+    lambda_runtime::run(service_fn(handler)).await
+}
+
+/// Relays each entry as it's written to the outbox table. Only the first
+/// failure is reported, the stream retries from there to keep entries in
+/// order.
+async fn handler(event: LambdaEvent<Event>) -> Result<DynamoDbEventResponse, Error> {
+    let mut response = DynamoDbEventResponse {
+        batch_item_failures: vec![],
+    };
+    for record in event.payload.records {
+        // Deletes by the relay and TTL come through too
+        if record.event_name != "INSERT" {
+            continue;
+        }
+        if let Err(e) = relay(&record).await {
+            println!("Failed {}: {e}", record.event_id);
+            response.batch_item_failures.push(DynamoDbBatchItemFailure {
+                item_identifier: record.change.sequence_number.clone(),
+            });
+            break;
+        }
+    }
+    Ok(response)
+}
+
+async fn relay(record: &EventRecord) -> Result<(), Error> {
+    let image: HashMap<String, AttributeValue> = record.change.new_image.clone().into();
+    let item = OutboxItem::from_map(&image)?;
+    service::relay_outbox::relay(&item).await?;
+    Ok(())
+}
@@ -1,36 +1,35 @@
-use aws_lambda_events::event::sqs::SqsEvent;
-use domain::{commands::CheckSessionTimeoutCommand, errors::LogicError};
+use api::sqs;
+use aws_lambda_events::event::sqs::{BatchItemFailure, SqsEvent, SqsMessage};
+use aws_lambda_events::sqs::SqsBatchResponse;
 use lambda_runtime::{service_fn, Error, LambdaEvent};
-use serde_json::{json, Value};
-use service;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
+    config::install_from_args();
     lambda_runtime::run(service_fn(handler)).await
 }
 
-async fn handler(event: LambdaEvent<SqsEvent>) -> Result<Value, Error> {
-    let sqs_event = event.payload;
-    let records = sqs_event.records;
-    println!("Received records: {:?}", records);
-
-    for record in records {
-        let body_str = record
-            .body
-            .ok_or(LogicError::LambdaError("body is required".to_string()))?;
-        let body = serde_json::from_str::<Value>(&body_str)
-            .map_err(|e| LogicError::LambdaError(format!("Failed to parse body: {}", e)))?;
-        let session_id = body["detail"]["session_id"]
-            .as_str()
-            .ok_or(LogicError::LambdaError(
-                "session_id is required".to_string(),
-            ))?;
-        println!("Received session_id: {:?}", session_id);
-        let command = CheckSessionTimeoutCommand {
-            session_id: session_id.to_string(),
-        };
-        let message = service::check_session_timeout::handler(&command).await?;
-        println!("Message: {:?}", message);
+/// Reports failed messages individually so only they are retried. After
+/// enough attempts SQS moves them to the dead-letter queue, which nothing
+/// reads from.
+async fn handler(event: LambdaEvent<SqsEvent>) -> Result<SqsBatchResponse, Error> {
+    let mut response = SqsBatchResponse::default();
+    for record in event.payload.records {
+        let message_id = record.message_id.clone().unwrap_or_default();
+        match handle(record).await {
+            Ok(message) => println!("Handled {message_id}: {message}"),
+            Err(e) => {
+                println!("Failed {message_id}: {e}");
+                response.batch_item_failures.push(BatchItemFailure {
+                    item_identifier: message_id,
+                });
+            }
+        }
     }
-    Ok(json!({"hello": "world"}))
+    Ok(response)
+}
+
+async fn handle(record: SqsMessage) -> Result<String, Error> {
+    let body = record.body.ok_or("body is required")?;
+    Ok(sqs::invoke(&body).await?)
 }
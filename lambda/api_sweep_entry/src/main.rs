@@ -0,0 +1,24 @@
+use lambda_runtime::{service_fn, Error, LambdaEvent};
+use serde_json::Value;
+use std::env;
+
+// Set by the Lambda runtime, so without it we're being run by hand
+const RUNTIME_API_ENV: &str = "AWS_LAMBDA_RUNTIME_API";
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    config::install_from_args();
+    if env::var(RUNTIME_API_ENV).is_err() {
+        let summary = service::sweep::handler().await?;
+        println!("Swept: {summary}");
+        return Ok(());
+    }
+    lambda_runtime::run(service_fn(handler)).await
+}
+
+/// Runs on a schedule, the event itself carries nothing we need.
+async fn handler(_event: LambdaEvent<Value>) -> Result<Value, Error> {
+    let summary = service::sweep::handler().await?;
+    println!("Swept: {summary}");
+    Ok(serde_json::from_str(&summary)?)
+}
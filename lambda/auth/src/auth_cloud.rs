@@ -0,0 +1,85 @@
+use crate::{get_key_id, Claims, IAuthenticator, JwkSet, JwtVerifier};
+use domain::errors::LogicError;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Uri};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+
+// Tokens carrying a made-up key ID must not turn into a JWKS request each
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct Authenticator {
+    client: Client<HttpsConnector<HttpConnector>, Body>,
+    jwks_url: String,
+    verifier: RwLock<JwtVerifier>,
+    last_refresh: Mutex<Option<Instant>>,
+}
+
+#[cfg_attr(feature = "in_memory", allow(unused))]
+impl Authenticator {
+    pub async fn new() -> Self {
+        let region_name = config::get().aws_region.clone();
+        let user_pool_id = &config::get().cognito_user_pool_id;
+        let client_id = &config::get().cognito_client_id;
+        let issuer = format!("https://cognito-idp.{region_name}.amazonaws.com/{user_pool_id}");
+        let jwks_url = format!("{issuer}/.well-known/jwks.json");
+        let connector = HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .https_only()
+            .enable_http1()
+            .build();
+        let client = Client::builder().build(connector);
+        let verifier = JwtVerifier::new(&issuer, client_id, JwkSet::default());
+        Authenticator {
+            client,
+            jwks_url,
+            verifier: RwLock::new(verifier),
+            last_refresh: Mutex::new(None),
+        }
+    }
+
+    async fn fetch_keys(&self) -> Result<JwkSet, LogicError> {
+        let uri = self
+            .jwks_url
+            .parse::<Uri>()
+            .map_err(|e| LogicError::AuthenticationError(e.to_string()))?;
+        let response = self
+            .client
+            .get(uri)
+            .await
+            .map_err(|e| LogicError::AuthenticationError(e.to_string()))?;
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|e| LogicError::AuthenticationError(e.to_string()))?;
+        serde_json::from_slice(&body).map_err(|e| LogicError::DeserializationError(e.to_string()))
+    }
+
+    async fn refresh_keys(&self, kid: &str) -> Result<(), LogicError> {
+        // Holding the lock through the fetch lets concurrent requests share it
+        let mut last_refresh = self.last_refresh.lock().await;
+        if self.verifier.read().await.has_key(kid) {
+            return Ok(());
+        }
+        if last_refresh.is_some_and(|at| at.elapsed() < MIN_REFRESH_INTERVAL) {
+            // Leaves the unknown key for verify to reject
+            return Ok(());
+        }
+        println!("Fetching JWKS");
+        *last_refresh = Some(Instant::now());
+        let keys = self.fetch_keys().await?;
+        self.verifier.write().await.set_keys(keys);
+        Ok(())
+    }
+}
+
+impl IAuthenticator for Authenticator {
+    async fn verify(&self, token: &str) -> Result<Claims, LogicError> {
+        // Cognito rotates its signing keys, so refresh the cache on an unknown key ID
+        let kid = get_key_id(token)?;
+        if !self.verifier.read().await.has_key(&kid) {
+            self.refresh_keys(&kid).await?;
+        }
+        self.verifier.read().await.verify(token)
+    }
+}
@@ -0,0 +1,14 @@
+use crate::Authenticator;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+static AUTHENTICATOR: OnceCell<Arc<Authenticator>> = OnceCell::const_new();
+
+pub async fn get() -> Arc<Authenticator> {
+    AUTHENTICATOR.get_or_init(init).await.clone()
+}
+
+async fn init() -> Arc<Authenticator> {
+    let authenticator = Authenticator::new().await;
+    Arc::new(authenticator)
+}
@@ -0,0 +1,20 @@
+use crate::{stub, Claims, IAuthenticator, JwtVerifier};
+use domain::errors::LogicError;
+
+pub struct Authenticator {
+    verifier: JwtVerifier,
+}
+
+#[cfg_attr(not(feature = "in_memory"), allow(unused))]
+impl Authenticator {
+    pub async fn new() -> Self {
+        let verifier = JwtVerifier::new(stub::ISSUER, stub::AUDIENCE, stub::jwks());
+        Authenticator { verifier }
+    }
+}
+
+impl IAuthenticator for Authenticator {
+    async fn verify(&self, token: &str) -> Result<Claims, LogicError> {
+        self.verifier.verify(token)
+    }
+}
@@ -0,0 +1,7 @@
+use crate::Claims;
+use domain::errors::LogicError;
+
+#[trait_variant::make(HttpService: Send)]
+pub trait IAuthenticator {
+    async fn verify(&self, token: &str) -> Result<Claims, LogicError>;
+}
@@ -0,0 +1,132 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use domain::errors::LogicError;
+use ring::signature::{RsaPublicKeyComponents, RSA_PKCS1_2048_8192_SHA256};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Claims {
+    pub sub: String,
+    pub iss: String,
+    pub aud: String,
+    pub exp: i64,
+    pub token_use: String,
+    pub email: Option<String>,
+    #[serde(rename = "cognito:username")]
+    pub username: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Jwk {
+    pub kid: String,
+    pub kty: String,
+    pub n: String,
+    pub e: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct JwkSet {
+    pub keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    kid: String,
+}
+
+/// Verifies RS256 signed ID tokens against a JWKS, as issued by Cognito.
+pub struct JwtVerifier {
+    issuer: String,
+    audience: String,
+    keys: JwkSet,
+}
+
+impl JwtVerifier {
+    pub fn new(issuer: &str, audience: &str, keys: JwkSet) -> Self {
+        JwtVerifier {
+            issuer: issuer.to_string(),
+            audience: audience.to_string(),
+            keys,
+        }
+    }
+
+    pub fn has_key(&self, kid: &str) -> bool {
+        self.keys.keys.iter().any(|k| k.kid == kid)
+    }
+
+    pub fn set_keys(&mut self, keys: JwkSet) {
+        self.keys = keys;
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, LogicError> {
+        let (signing_input, signature) = token
+            .rsplit_once('.')
+            .ok_or(unauthenticated("Malformed token"))?;
+        let (header, payload) = signing_input
+            .split_once('.')
+            .ok_or(unauthenticated("Malformed token"))?;
+
+        let header: Header = decode_json(header)?;
+        if header.alg != "RS256" {
+            return Err(unauthenticated("Unsupported algorithm"));
+        }
+        let key = self
+            .keys
+            .keys
+            .iter()
+            .find(|k| k.kid == header.kid && k.kty == "RSA")
+            .ok_or(unauthenticated("Unknown signing key"))?;
+        let public_key = RsaPublicKeyComponents {
+            n: decode_base64(&key.n)?,
+            e: decode_base64(&key.e)?,
+        };
+        public_key
+            .verify(
+                &RSA_PKCS1_2048_8192_SHA256,
+                signing_input.as_bytes(),
+                &decode_base64(signature)?,
+            )
+            .map_err(|_| unauthenticated("Invalid signature"))?;
+
+        let claims: Claims = decode_json(payload)?;
+        if claims.iss != self.issuer {
+            return Err(unauthenticated("Invalid issuer"));
+        }
+        if claims.aud != self.audience {
+            return Err(unauthenticated("Invalid audience"));
+        }
+        if claims.token_use != "id" {
+            return Err(unauthenticated("Not an ID token"));
+        }
+        if claims.exp <= Utc::now().timestamp() {
+            return Err(unauthenticated("Token expired"));
+        }
+        Ok(claims)
+    }
+}
+
+pub fn get_key_id(token: &str) -> Result<String, LogicError> {
+    let header = token
+        .split('.')
+        .next()
+        .ok_or(unauthenticated("Malformed token"))?;
+    let header: Header = decode_json(header)?;
+    Ok(header.kid)
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, LogicError> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|_| unauthenticated("Malformed token"))
+}
+
+fn decode_json<T: DeserializeOwned>(value: &str) -> Result<T, LogicError> {
+    serde_json::from_slice(&decode_base64(value)?).map_err(|_| unauthenticated("Malformed token"))
+}
+
+fn unauthenticated(reason: &str) -> LogicError {
+    LogicError::AuthenticationError(reason.to_string())
+}
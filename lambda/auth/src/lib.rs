@@ -0,0 +1,16 @@
+mod auth_cloud;
+mod auth_instance;
+#[cfg(any(test, feature = "in_memory"))]
+mod auth_local;
+mod auth_trait;
+mod jwt;
+#[cfg(any(test, feature = "in_memory"))]
+pub mod stub;
+
+#[cfg(not(feature = "in_memory"))]
+pub use auth_cloud::*;
+pub use auth_instance::*;
+#[cfg(feature = "in_memory")]
+pub use auth_local::*;
+pub use auth_trait::*;
+pub use jwt::*;
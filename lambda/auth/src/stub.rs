@@ -0,0 +1,53 @@
+//! A signing key and matching JWKS used in place of Cognito when running
+//! locally and in tests. The cloud authenticator never trusts this key.
+use crate::JwkSet;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use ring::rand::SystemRandom;
+use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+use serde_json::{json, Value};
+
+pub const AUDIENCE: &str = "local-client";
+pub const ISSUER: &str = "local-jwks-stub";
+const KEY_ID: &str = "local-stub";
+
+pub fn jwks() -> JwkSet {
+    serde_json::from_str(include_str!("stub/jwks.json")).expect("Invalid stub JWKS")
+}
+
+pub fn sign(claims: &Value) -> String {
+    let key_pair = RsaKeyPair::from_pkcs8(include_bytes!("stub/private_key.pk8"))
+        .expect("Invalid stub private key");
+    let header = json!({"alg": "RS256", "kid": KEY_ID, "typ": "JWT"});
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(claims.to_string())
+    );
+    let mut signature = vec![0; key_pair.public().modulus_len()];
+    key_pair
+        .sign(
+            &RSA_PKCS1_SHA256,
+            &SystemRandom::new(),
+            signing_input.as_bytes(),
+            &mut signature,
+        )
+        .expect("Failed to sign stub token");
+    format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature))
+}
+
+pub fn claims_for(account_id: &str) -> Value {
+    json!({
+        "sub": account_id,
+        "iss": ISSUER,
+        "aud": AUDIENCE,
+        "exp": Utc::now().timestamp() + 3600,
+        "token_use": "id",
+        "email": format!("{}@example.com", account_id),
+    })
+}
+
+pub fn token_for(account_id: &str) -> String {
+    sign(&claims_for(account_id))
+}
@@ -0,0 +1,217 @@
+use crate::{RateLimits, Settings, KEYS};
+use domain::errors::{LogicError, ValidationError};
+use domain::values::GAME_CODE_LENGTH;
+use serde::Serialize;
+use std::net::SocketAddr;
+
+/// Everything the binaries and backends read from their environment,
+/// validated once at startup.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Config {
+    pub api_gateway_url: String,
+    pub aws_region: String,
+    pub bind_address: SocketAddr,
+    /// Connections not saved for this long are assumed dead and swept.
+    pub connection_ttl_secs: u64,
+    pub cognito_client_id: String,
+    pub cognito_user_pool_id: String,
+    /// Prefixes the source of every published event, as in
+    /// `{event_source}.Game`.
+    pub event_source: String,
+    pub game_code_length: usize,
+    /// How long a game's code stays reserved after the game was last saved,
+    /// after which the game counts as abandoned, is swept and its code can be
+    /// reused.
+    pub game_code_ttl_secs: u64,
+    pub game_table_name: String,
+    pub leaderboard_table_name: String,
+    /// The account the local server's fake authorizer signs requests in as,
+    /// unless they send an `X-Fake-Sub` header.
+    pub local_auth_email: String,
+    /// Whether the fake authorizer rejects requests without the header.
+    pub local_auth_require_header: bool,
+    pub local_auth_sub: String,
+    pub local_auth_username: String,
+    pub metrics_namespace: String,
+    pub name_table_name: String,
+    pub outbox_table_name: String,
+    pub profile_table_name: String,
+    pub rate_limit_table_name: String,
+    pub rate_limits: RateLimits,
+    /// Sessions not saved for this long are swept once disconnected.
+    pub session_ttl_secs: u64,
+    pub websocket_table_name: String,
+}
+
+impl Config {
+    /// Reports every missing or invalid setting, not just the first.
+    pub fn from_settings(settings: &Settings) -> Result<Self, LogicError> {
+        let mut errors = vec![];
+        for key in KEYS {
+            let required = !(cfg!(feature = "in_memory") && key.cloud_only);
+            if required && settings.get(key.name).is_none() {
+                errors.push(ValidationError::new(
+                    key.name,
+                    &format!(
+                        "Missing, set ${} or --{}",
+                        key.env,
+                        key.name.replace('_', "-")
+                    ),
+                ));
+            }
+        }
+        let value = |name: &str| settings.get(name).unwrap_or_default().to_string();
+
+        let bind_address = settings
+            .get("bind_address")
+            .unwrap_or_default()
+            .parse::<SocketAddr>();
+        if let (Some(_), Err(e)) = (settings.get("bind_address"), &bind_address) {
+            errors.push(ValidationError::new("bind_address", &e.to_string()));
+        }
+        let api_gateway_url = value("api_gateway_url");
+        let schemes = ["http://", "https://", "ws://", "wss://"];
+        if !api_gateway_url.is_empty() && !schemes.iter().any(|s| api_gateway_url.starts_with(s)) {
+            errors.push(ValidationError::new(
+                "api_gateway_url",
+                "Must be an http(s) or ws(s) URL",
+            ));
+        }
+        let game_code_length = value("game_code_length").parse::<usize>();
+        if !matches!(game_code_length, Ok(ref length) if GAME_CODE_LENGTH.contains(length)) {
+            errors.push(ValidationError::new(
+                "game_code_length",
+                &format!(
+                    "Must be a number from {} to {}",
+                    GAME_CODE_LENGTH.start(),
+                    GAME_CODE_LENGTH.end()
+                ),
+            ));
+        }
+        let mut ttl = |name: &str| {
+            let secs = value(name).parse::<u64>().unwrap_or_default();
+            if secs == 0 {
+                errors.push(ValidationError::new(
+                    name,
+                    "Must be a positive number of seconds",
+                ));
+            }
+            secs
+        };
+        let connection_ttl_secs = ttl("connection_ttl_secs");
+        let game_code_ttl_secs = ttl("game_code_ttl_secs");
+        let session_ttl_secs = ttl("session_ttl_secs");
+        let local_auth_require_header = value("local_auth_require_header").parse::<bool>();
+        if local_auth_require_header.is_err() {
+            errors.push(ValidationError::new(
+                "local_auth_require_header",
+                "Must be true or false",
+            ));
+        }
+        for name in ["local_auth_sub", "local_auth_username"] {
+            if settings.get(name).is_some_and(str::is_empty) {
+                errors.push(ValidationError::new(name, "Must not be empty"));
+            }
+        }
+        if !value("local_auth_email").contains('@') {
+            errors.push(ValidationError::new(
+                "local_auth_email",
+                "Must be an email address",
+            ));
+        }
+        let rate_limits = RateLimits::parse(&value("rate_limits"));
+        if let Err(e) = &rate_limits {
+            errors.extend(e.iter().cloned());
+        }
+
+        if !errors.is_empty() {
+            return Err(LogicError::ConfigError(errors));
+        }
+        Ok(Config {
+            api_gateway_url,
+            aws_region: value("aws_region"),
+            bind_address: bind_address.unwrap(),
+            connection_ttl_secs,
+            cognito_client_id: value("cognito_client_id"),
+            cognito_user_pool_id: value("cognito_user_pool_id"),
+            event_source: value("event_source"),
+            game_code_length: game_code_length.unwrap(),
+            game_code_ttl_secs,
+            game_table_name: value("game_table_name"),
+            leaderboard_table_name: value("leaderboard_table_name"),
+            local_auth_email: value("local_auth_email"),
+            local_auth_require_header: local_auth_require_header.unwrap(),
+            local_auth_sub: value("local_auth_sub"),
+            local_auth_username: value("local_auth_username"),
+            metrics_namespace: value("metrics_namespace"),
+            name_table_name: value("name_table_name"),
+            outbox_table_name: value("outbox_table_name"),
+            profile_table_name: value("profile_table_name"),
+            rate_limit_table_name: value("rate_limit_table_name"),
+            rate_limits: rate_limits.unwrap(),
+            session_ttl_secs,
+            websocket_table_name: value("websocket_table_name"),
+        })
+    }
+
+    /// The API Gateway Management API endpoint for posting to connections.
+    pub fn management_url(&self) -> String {
+        self.api_gateway_url
+            .replacen("wss://", "https://", 1)
+            .replacen("ws://", "http://", 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tables() -> Settings {
+        Settings::defaults()
+            .with("game_table_name", "Game")
+            .with("leaderboard_table_name", "Leaderboard")
+            .with("outbox_table_name", "Outbox")
+            .with("profile_table_name", "Profile")
+            .with("rate_limit_table_name", "RateLimit")
+            .with("websocket_table_name", "Websocket")
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let settings = Settings::new()
+            .with("bind_address", "localhost")
+            .with("api_gateway_url", "ftp://example.com")
+            .with("game_code_length", "12")
+            .with("local_auth_require_header", "yes");
+        let Err(LogicError::ConfigError(errors)) = Config::from_settings(&settings) else {
+            panic!("Expected config errors");
+        };
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert!(fields.contains(&"game_table_name"));
+        assert!(fields.contains(&"websocket_table_name"));
+        assert!(fields.contains(&"bind_address"));
+        assert!(fields.contains(&"api_gateway_url"));
+        assert!(fields.contains(&"game_code_length"));
+        assert!(fields.contains(&"local_auth_require_header"));
+    }
+
+    #[test]
+    fn builds_from_valid_settings() {
+        let settings = tables()
+            .with(
+                "api_gateway_url",
+                "wss://abc.execute-api.eu-west-2.amazonaws.com/v1",
+            )
+            .with("aws_region", "eu-west-2")
+            .with("cognito_client_id", "client")
+            .with("cognito_user_pool_id", "pool")
+            .with("name_table_name", "Names");
+        let config = Config::from_settings(&settings).unwrap();
+        assert_eq!(config.game_table_name, "Game");
+        assert_eq!(config.bind_address.port(), 8080);
+        assert_eq!(
+            config.management_url(),
+            "https://abc.execute-api.eu-west-2.amazonaws.com/v1"
+        );
+    }
+}
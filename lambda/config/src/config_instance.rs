@@ -0,0 +1,80 @@
+use crate::{usage, Config, Settings, CONFIG_FILE_ENV};
+use domain::errors::LogicError;
+use std::env;
+use std::process;
+use std::sync::OnceLock;
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The config installed at startup. Tests and anything else that never
+/// called `install` read it from the environment.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let settings = Settings::defaults().merge(Settings::from_env());
+        Config::from_settings(&settings).unwrap_or_else(|e| panic!("{e}"))
+    })
+}
+
+/// Settings gathered from every source, not yet validated.
+pub struct Sources {
+    pub settings: Settings,
+    pub print_config: bool,
+}
+
+/// Layers the settings as defaults, `binary_defaults`, the config file, the
+/// environment, then the command line. Prints usage and exits for `--help`.
+pub fn read_sources(binary_defaults: Settings) -> Result<Sources, LogicError> {
+    let args: Vec<String> = env::args().collect();
+    let (cli, flags) = Settings::from_args(&args)?;
+    if flags.help {
+        println!("{}", usage(&args[0]));
+        process::exit(0);
+    }
+    let config_file = flags.config_file.or(env::var(CONFIG_FILE_ENV).ok());
+    let file = match config_file {
+        Some(path) => Settings::from_file(&path)?,
+        None => Settings::new(),
+    };
+    let settings = Settings::defaults()
+        .merge(binary_defaults)
+        .merge(file)
+        .merge(Settings::from_env())
+        .merge(cli);
+    Ok(Sources {
+        settings,
+        print_config: flags.print_config,
+    })
+}
+
+/// Validates and installs the config. With `--print-config` it prints the
+/// result and exits, and any error exits before the server starts.
+pub fn install(sources: Result<Sources, LogicError>) -> &'static Config {
+    let sources = sources.unwrap_or_else(|e| exit_with(e));
+    let config = Config::from_settings(&sources.settings).unwrap_or_else(|e| exit_with(e));
+    if sources.print_config {
+        let json = serde_json::to_string_pretty(&config).unwrap();
+        println!("{json}");
+        process::exit(0);
+    }
+    if CONFIG.set(config).is_err() {
+        println!("Config was read before it was installed");
+    }
+    get()
+}
+
+pub fn install_from_args() -> &'static Config {
+    install(read_sources(Settings::new()))
+}
+
+fn exit_with(error: LogicError) -> ! {
+    match error {
+        LogicError::ConfigError(errors) => {
+            eprintln!("Invalid configuration:");
+            for error in errors {
+                eprintln!("  {error}");
+            }
+        }
+        e => eprintln!("{e}"),
+    }
+    process::exit(2)
+}
@@ -0,0 +1,9 @@
+mod config;
+mod config_instance;
+mod rate_limits;
+mod settings;
+
+pub use config::*;
+pub use config_instance::*;
+pub use rate_limits::*;
+pub use settings::*;
@@ -0,0 +1,117 @@
+use domain::errors::ValidationError;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Actions without their own limit share this one.
+pub const DEFAULT_ACTION: &str = "default";
+
+/// Allows `requests` every `period_secs`, refilling one at a time, so a quiet
+/// client can burst up to `requests` at once.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct RateLimit {
+    pub requests: i32,
+    pub period_secs: u64,
+}
+
+impl RateLimit {
+    pub fn new(requests: i32, period_secs: u64) -> Self {
+        RateLimit {
+            requests,
+            period_secs,
+        }
+    }
+
+    /// How long a client waits for each request to be refilled.
+    pub fn refill_interval(&self) -> Duration {
+        Duration::from_secs(self.period_secs) / self.requests as u32
+    }
+
+    /// Parses `10/60s`.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (requests, period) = value
+            .split_once('/')
+            .ok_or(format!("Expected requests/period, got {value}"))?;
+        let requests = requests
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| format!("Invalid request count {requests}"))?;
+        let period = period.trim();
+        let period_secs = period
+            .strip_suffix('s')
+            .unwrap_or(period)
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid period {period}"))?;
+        if requests < 1 || period_secs < 1 {
+            return Err(format!("Limit must be positive, got {value}"));
+        }
+        Ok(RateLimit::new(requests, period_secs))
+    }
+}
+
+/// Limits per websocket action, keyed by the action name clients send.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RateLimits(BTreeMap<String, RateLimit>);
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        let limits = [
+            // Each game burns a game code
+            ("createGame", RateLimit::new(3, 60)),
+            (DEFAULT_ACTION, RateLimit::new(20, 10)),
+            ("rollDice", RateLimit::new(10, 10)),
+            ("setNickname", RateLimit::new(5, 10)),
+        ];
+        let limits = limits.map(|(action, limit)| (action.to_string(), limit));
+        RateLimits(BTreeMap::from(limits))
+    }
+}
+
+impl RateLimits {
+    /// Parses `rollDice=10/10s,createGame=3/60s` on top of the defaults.
+    pub fn parse(value: &str) -> Result<Self, Vec<ValidationError>> {
+        let mut limits = RateLimits::default();
+        let mut errors = vec![];
+        for entry in value.split(',').filter(|e| !e.trim().is_empty()) {
+            let parsed = entry
+                .split_once('=')
+                .ok_or(format!("Expected action=limit, got {entry}"))
+                .and_then(|(action, limit)| Ok((action.trim(), RateLimit::parse(limit)?)));
+            match parsed {
+                Ok((action, limit)) => {
+                    limits.0.insert(action.to_string(), limit);
+                }
+                Err(reason) => errors.push(ValidationError::new("rate_limits", &reason)),
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(limits),
+            false => Err(errors),
+        }
+    }
+
+    pub fn for_action(&self, action: &str) -> RateLimit {
+        match self.0.get(action) {
+            Some(limit) => *limit,
+            None => self.0[DEFAULT_ACTION],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_defaults_per_action() {
+        let limits = RateLimits::parse("rollDice=2/5s, getProfile=1/60").unwrap();
+        assert_eq!(limits.for_action("rollDice"), RateLimit::new(2, 5));
+        assert_eq!(limits.for_action("getProfile"), RateLimit::new(1, 60));
+        assert_eq!(limits.for_action("createGame"), RateLimit::new(3, 60));
+        assert_eq!(limits.for_action("getSession"), RateLimit::new(20, 10));
+        assert_eq!(
+            RateLimits::parse("rollDice=0/5s,oops").unwrap_err().len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use domain::errors::{LogicError, ValidationError};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+
+/// A setting, named the same in config files and on the command line (as
+/// `--kebab-case`), and read from its own environment variable.
+pub struct Key {
+    pub name: &'static str,
+    pub env: &'static str,
+    /// Only the AWS backends use it, so in memory builds can leave it out.
+    pub cloud_only: bool,
+}
+
+pub const KEYS: &[Key] = &[
+    key("api_gateway_url", "API_GATEWAY_URL", true),
+    key("aws_region", "AWS_REGION", true),
+    key("bind_address", "BIND_ADDRESS", false),
+    key("connection_ttl_secs", "CONNECTION_TTL_SECS", false),
+    key("cognito_client_id", "COGNITO_CLIENT_ID", true),
+    key("cognito_user_pool_id", "COGNITO_USER_POOL_ID", true),
+    key("event_source", "EVENT_SOURCE", false),
+    key("game_code_length", "GAME_CODE_LENGTH", false),
+    key("game_code_ttl_secs", "GAME_CODE_TTL_SECS", false),
+    key("game_table_name", "GAME_TABLE_NAME", false),
+    key("leaderboard_table_name", "LEADERBOARD_TABLE_NAME", false),
+    key("local_auth_email", "LOCAL_AUTH_EMAIL", false),
+    key(
+        "local_auth_require_header",
+        "LOCAL_AUTH_REQUIRE_HEADER",
+        false,
+    ),
+    key("local_auth_sub", "LOCAL_AUTH_SUB", false),
+    key("local_auth_username", "LOCAL_AUTH_USERNAME", false),
+    key("metrics_namespace", "METRICS_NAMESPACE", false),
+    key("name_table_name", "NAME_TABLE_NAME", true),
+    key("outbox_table_name", "OUTBOX_TABLE_NAME", false),
+    key("profile_table_name", "PROFILE_TABLE_NAME", false),
+    key("rate_limit_table_name", "RATE_LIMIT_TABLE_NAME", false),
+    key("rate_limits", "RATE_LIMITS", false),
+    key("session_ttl_secs", "SESSION_TTL_SECS", false),
+    key("websocket_table_name", "WEBSOCKET_TABLE_NAME", false),
+];
+
+const fn key(name: &'static str, env: &'static str, cloud_only: bool) -> Key {
+    Key {
+        name,
+        env,
+        cloud_only,
+    }
+}
+
+pub const CONFIG_FILE_ENV: &str = "CONFIG_FILE";
+
+/// One layer of raw settings. Layers are merged before being validated into
+/// a `Config`, so later sources override earlier ones.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Settings {
+    values: BTreeMap<String, String>,
+}
+
+/// What the command line asked for, besides settings.
+#[derive(Debug, Default, PartialEq)]
+pub struct Flags {
+    pub config_file: Option<String>,
+    pub help: bool,
+    pub print_config: bool,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Settings::default()
+    }
+
+    pub fn defaults() -> Self {
+        Settings::new()
+            .with("bind_address", "127.0.0.1:8080")
+            // API Gateway closes connections after 2 hours
+            .with("connection_ttl_secs", "10800")
+            .with("event_source", "RustLambda-Dev")
+            .with("game_code_length", "4")
+            .with("game_code_ttl_secs", "86400")
+            .with("local_auth_email", "local@example.com")
+            .with("local_auth_require_header", "false")
+            .with("local_auth_sub", "local-account")
+            .with("local_auth_username", "local")
+            .with("metrics_namespace", "RustLambda")
+            .with("rate_limits", "")
+            .with("session_ttl_secs", "86400")
+    }
+
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.values.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(|s| s.as_str())
+    }
+
+    pub fn set_default(&mut self, name: &str, value: &str) {
+        self.values
+            .entry(name.to_string())
+            .or_insert(value.to_string());
+    }
+
+    /// Values in `other` take precedence.
+    pub fn merge(mut self, other: Settings) -> Self {
+        self.values.extend(other.values);
+        self
+    }
+
+    /// Unset and empty variables are both treated as missing.
+    pub fn from_env() -> Self {
+        let mut settings = Settings::new();
+        for key in KEYS {
+            match env::var(key.env) {
+                Ok(value) if !value.is_empty() => {
+                    settings.values.insert(key.name.to_string(), value);
+                }
+                _ => (),
+            }
+        }
+        settings
+    }
+
+    /// Reads a JSON object of setting names to strings.
+    pub fn from_file(path: &str) -> Result<Self, LogicError> {
+        let text = fs::read_to_string(path).map_err(|e| invalid("config_file", &e.to_string()))?;
+        Settings::from_json(&text)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, LogicError> {
+        let value: Value =
+            serde_json::from_str(text).map_err(|e| invalid("config_file", &e.to_string()))?;
+        let object = value
+            .as_object()
+            .ok_or(invalid("config_file", "Must be a JSON object"))?;
+        let mut settings = Settings::new();
+        let mut errors = vec![];
+        for (name, value) in object {
+            if find_key(name).is_none() {
+                errors.push(ValidationError::new(name, "Unknown setting"));
+                continue;
+            }
+            match value.as_str() {
+                Some(value) => {
+                    settings.values.insert(name.clone(), value.to_string());
+                }
+                None => errors.push(ValidationError::new(name, "Must be a string")),
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(settings),
+            false => Err(LogicError::ConfigError(errors)),
+        }
+    }
+
+    /// Parses `--name value` and `--name=value` pairs, skipping the program
+    /// name.
+    pub fn from_args(args: &[String]) -> Result<(Self, Flags), LogicError> {
+        let mut settings = Settings::new();
+        let mut flags = Flags::default();
+        let mut errors = vec![];
+        let mut args = args.iter().skip(1);
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                errors.push(ValidationError::new(arg, "Unexpected argument"));
+                continue;
+            };
+            let (name, inline_value) = match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (option, None),
+            };
+            match name {
+                "help" => flags.help = true,
+                "print-config" => flags.print_config = true,
+                _ => {
+                    let value = inline_value.or_else(|| args.next().cloned());
+                    let Some(value) = value else {
+                        errors.push(ValidationError::new(arg, "Missing value"));
+                        continue;
+                    };
+                    if name == "config" {
+                        flags.config_file = Some(value);
+                        continue;
+                    }
+                    let setting = name.replace('-', "_");
+                    match find_key(&setting) {
+                        Some(key) => {
+                            settings.values.insert(key.name.to_string(), value);
+                        }
+                        None => errors.push(ValidationError::new(arg, "Unknown option")),
+                    }
+                }
+            }
+        }
+        match errors.is_empty() {
+            true => Ok((settings, flags)),
+            false => Err(LogicError::ConfigError(errors)),
+        }
+    }
+}
+
+pub fn find_key(name: &str) -> Option<&'static Key> {
+    KEYS.iter().find(|key| key.name == name)
+}
+
+pub fn usage(program: &str) -> String {
+    let mut lines = vec![
+        format!("Usage: {program} [--config FILE] [--print-config] [--NAME VALUE]..."),
+        String::new(),
+        format!("Settings are read from defaults, the config file (or ${CONFIG_FILE_ENV}),"),
+        "the environment, then the command line, each overriding the last.".to_string(),
+        String::new(),
+    ];
+    for key in KEYS {
+        let option = format!("--{}", key.name.replace('_', "-"));
+        lines.push(format!("  {option:<26} ${}", key.env));
+    }
+    lines.join("\n")
+}
+
+fn invalid(field: &str, reason: &str) -> LogicError {
+    LogicError::ConfigError(vec![ValidationError::new(field, reason)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_command_line() {
+        let (settings, flags) = Settings::from_args(&args(&[
+            "api_local_entry",
+            "--bind-address",
+            "0.0.0.0:9000",
+            "--game-table-name=Games",
+            "--config",
+            "local.json",
+            "--print-config",
+        ]))
+        .unwrap();
+        assert_eq!(settings.get("bind_address"), Some("0.0.0.0:9000"));
+        assert_eq!(settings.get("game_table_name"), Some("Games"));
+        assert_eq!(flags.config_file.as_deref(), Some("local.json"));
+        assert!(flags.print_config);
+    }
+
+    #[test]
+    fn reports_every_bad_argument() {
+        let result =
+            Settings::from_args(&args(&["bin", "--colour", "red", "stray", "--aws-region"]));
+        let Err(LogicError::ConfigError(errors)) = result else {
+            panic!("Expected config errors");
+        };
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn later_layers_override_earlier_ones() {
+        let file =
+            Settings::from_json(r#"{"game_table_name": "FileGames", "aws_region": "eu-west-1"}"#)
+                .unwrap();
+        let cli = Settings::new().with("game_table_name", "CliGames");
+        let settings = Settings::defaults().merge(file).merge(cli);
+        assert_eq!(settings.get("game_table_name"), Some("CliGames"));
+        assert_eq!(settings.get("aws_region"), Some("eu-west-1"));
+        assert_eq!(settings.get("bind_address"), Some("127.0.0.1:8080"));
+        assert!(Settings::from_json(r#"{"gmae_table_name": "Games"}"#).is_err());
+    }
+}
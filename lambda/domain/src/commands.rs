@@ -1,3 +1,7 @@
+use crate::values::{
+    AccountId, ConnectionId, GameCode, IdToken, LeaderboardStat, Nickname, Page, ReconnectToken,
+    SessionId,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -10,30 +14,45 @@ pub struct SayGoodbyeCommand {
     pub name: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AuthenticateCommand {
+    pub connection_id: ConnectionId,
+    pub session_id: SessionId,
+    pub token: IdToken,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreateConnectionCommand {
-    pub connection_id: String,
+    pub connection_id: ConnectionId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreateGameCommand {
-    pub connection_id: String,
-    pub session_id: String,
+    pub connection_id: ConnectionId,
+    pub session_id: SessionId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CreateSessionCommand {
-    pub connection_id: String,
+    pub connection_id: ConnectionId,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct CheckSessionTimeoutCommand {
     pub session_id: String,
 }
 
+/// A command the scheduler runs later, tagged so it survives a trip through
+/// a queue.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ScheduledCommand {
+    CheckSessionTimeout(CheckSessionTimeoutCommand),
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DestroyConnectionCommand {
-    pub connection_id: String,
+    pub connection_id: ConnectionId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -42,6 +61,51 @@ pub struct DestroySessionCommand {
     pub session_id: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetAccountCommand {
+    pub account_id: AccountId,
+    pub email: String,
+    pub username: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetAccountProfileCommand {
+    pub account_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetGameCommand {
+    pub account_id: AccountId,
+    pub game_id: GameCode,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetGameRoundsCommand {
+    pub account_id: AccountId,
+    pub game_id: GameCode,
+    pub page: Page,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetLeaderboardCommand {
+    pub connection_id: ConnectionId,
+    pub game_id: Option<GameCode>,
+    pub page: Page,
+    pub stat: LeaderboardStat,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetSessionDetailsCommand {
+    pub account_id: AccountId,
+    pub session_id: SessionId,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetProfileCommand {
+    pub connection_id: ConnectionId,
+    pub session_id: SessionId,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LeaveGameCommand {
     pub session_id: String,
@@ -50,31 +114,34 @@ pub struct LeaveGameCommand {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct NewRoundCommand {
-    pub connection_id: String,
-    pub session_id: String,
+    pub connection_id: ConnectionId,
+    pub session_id: SessionId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RollDiceCommand {
-    pub connection_id: String,
-    pub session_id: String,
+    pub connection_id: ConnectionId,
+    pub session_id: SessionId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct SendGameStateNotificationCommand {
-    pub game_id: String,
+pub struct QueryLeaderboardCommand {
+    pub game_id: Option<GameCode>,
+    pub page: Page,
+    pub stat: LeaderboardStat,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SetNicknameCommand {
-    pub connection_id: String,
-    pub session_id: String,
-    pub account_id: Option<String>,
-    pub nickname: String,
+    pub connection_id: ConnectionId,
+    pub session_id: SessionId,
+    pub account_id: Option<AccountId>,
+    pub nickname: Nickname,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SetSessionCommand {
-    pub connection_id: String,
-    pub session_id: String,
+    pub connection_id: ConnectionId,
+    pub reconnect_token: ReconnectToken,
+    pub session_id: SessionId,
 }
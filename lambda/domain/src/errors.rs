@@ -1,29 +1,95 @@
+use serde::Serialize;
 use std::error::Error;
 use std::fmt;
 
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ValidationError {
+    pub field: String,
+    pub reason: String,
+}
+
+impl ValidationError {
+    pub fn new(field: &str, reason: &str) -> Self {
+        ValidationError {
+            field: field.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.reason)
+    }
+}
+impl Error for ValidationError {}
+
 #[derive(Debug, PartialEq)]
 pub enum LogicError {
+    AuthenticationError(String),
     ConditionalCheckFailed(String),
+    ConfigError(Vec<ValidationError>),
+    /// The client closed the websocket, so it can't be sent to.
+    ConnectionGone(String),
     DeleteItemError(String),
     DeserializationError(String),
     EventPublishingError(String),
     GetItemError(String),
     InvalidGameState(String),
+    ItemNotFound(String),
     LambdaError(String),
+    MethodNotAllowed(String),
     NotAllowed,
     ParseItemError(String),
+    RateLimited(String),
     RestError(String),
     SerializationError(String),
     UpdateItemError(String),
+    ValidationError(ValidationError),
     WebsocketError(String),
 }
 
+impl LogicError {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogicError::AuthenticationError(_) => "AuthenticationError",
+            LogicError::ConditionalCheckFailed(_) => "ConditionalCheckFailed",
+            LogicError::ConfigError(_) => "ConfigError",
+            LogicError::ConnectionGone(_) => "ConnectionGone",
+            LogicError::DeleteItemError(_) => "DeleteItemError",
+            LogicError::DeserializationError(_) => "DeserializationError",
+            LogicError::EventPublishingError(_) => "EventPublishingError",
+            LogicError::GetItemError(_) => "GetItemError",
+            LogicError::InvalidGameState(_) => "InvalidGameState",
+            LogicError::ItemNotFound(_) => "ItemNotFound",
+            LogicError::LambdaError(_) => "LambdaError",
+            LogicError::MethodNotAllowed(_) => "MethodNotAllowed",
+            LogicError::NotAllowed => "NotAllowed",
+            LogicError::ParseItemError(_) => "ParseItemError",
+            LogicError::RateLimited(_) => "RateLimited",
+            LogicError::RestError(_) => "RestError",
+            LogicError::SerializationError(_) => "SerializationError",
+            LogicError::UpdateItemError(_) => "UpdateItemError",
+            LogicError::ValidationError(_) => "ValidationError",
+            LogicError::WebsocketError(_) => "WebsocketError",
+        }
+    }
+}
+
 impl fmt::Display for LogicError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            LogicError::AuthenticationError(ref msg) => {
+                write!(f, "[AuthenticationError] {}", msg)
+            }
             LogicError::ConditionalCheckFailed(ref msg) => {
                 write!(f, "[ConditionalCheckFailed] {}", msg)
             }
+            LogicError::ConfigError(ref errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "[ConfigError] {}", errors.join("; "))
+            }
+            LogicError::ConnectionGone(ref msg) => write!(f, "[ConnectionGone] {}", msg),
             LogicError::DeleteItemError(ref msg) => write!(f, "[DeleteItemError] {}", msg),
             LogicError::DeserializationError(ref msg) => {
                 write!(f, "[DeserializationError] {}", msg)
@@ -33,14 +99,24 @@ impl fmt::Display for LogicError {
             }
             LogicError::GetItemError(ref msg) => write!(f, "[GetItemError] {}", msg),
             LogicError::InvalidGameState(ref msg) => write!(f, "[InvalidGameState] {}", msg),
+            LogicError::ItemNotFound(ref msg) => write!(f, "[ItemNotFound] {}", msg),
             LogicError::LambdaError(ref msg) => write!(f, "[LambdaError] {}", msg),
+            LogicError::MethodNotAllowed(ref msg) => write!(f, "[MethodNotAllowed] {}", msg),
             LogicError::NotAllowed => write!(f, "[NotAllowed]"),
             LogicError::ParseItemError(ref msg) => write!(f, "[ParseError] {}", msg),
+            LogicError::RateLimited(ref msg) => write!(f, "[RateLimited] {}", msg),
             LogicError::RestError(ref msg) => write!(f, "[RestError] {}", msg),
             LogicError::SerializationError(ref msg) => write!(f, "[SerializationError] {}", msg),
             LogicError::UpdateItemError(ref msg) => write!(f, "[UpdateItemError] {}", msg),
+            LogicError::ValidationError(ref error) => write!(f, "[ValidationError] {}", error),
             LogicError::WebsocketError(ref msg) => write!(f, "[WebsocketError] {}", msg),
         }
     }
 }
 impl Error for LogicError {}
+
+impl From<ValidationError> for LogicError {
+    fn from(error: ValidationError) -> Self {
+        LogicError::ValidationError(error)
+    }
+}
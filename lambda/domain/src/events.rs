@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// A change to the game's state, published for anything downstream to react
+/// to. The variant name is the detail-type and its fields are the detail.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "detail-type", content = "detail")]
+pub enum DomainEvent {
+    GameCreated {
+        game_id: String,
+        session_id: String,
+    },
+    PlayerJoined {
+        game_id: String,
+        session_id: String,
+        nickname: String,
+    },
+    PlayerLeft {
+        game_id: String,
+        session_id: String,
+    },
+    DiceRolled {
+        game_id: String,
+        session_id: String,
+        dice: Vec<i32>,
+        turn_finished: bool,
+    },
+    RoundFinished {
+        game_id: String,
+        round_id: i32,
+        mr_eleven: Option<String>,
+    },
+    NewRoundStarted {
+        game_id: String,
+    },
+    Disconnected {
+        session_id: String,
+    },
+    SessionExpired {
+        session_id: String,
+    },
+    NicknameChanged {
+        session_id: String,
+        nickname: String,
+    },
+}
+
+impl DomainEvent {
+    pub fn detail_type(&self) -> &'static str {
+        match self {
+            DomainEvent::GameCreated { .. } => "GameCreated",
+            DomainEvent::PlayerJoined { .. } => "PlayerJoined",
+            DomainEvent::PlayerLeft { .. } => "PlayerLeft",
+            DomainEvent::DiceRolled { .. } => "DiceRolled",
+            DomainEvent::RoundFinished { .. } => "RoundFinished",
+            DomainEvent::NewRoundStarted { .. } => "NewRoundStarted",
+            DomainEvent::Disconnected { .. } => "Disconnected",
+            DomainEvent::SessionExpired { .. } => "SessionExpired",
+            DomainEvent::NicknameChanged { .. } => "NicknameChanged",
+        }
+    }
+
+    /// The part of the system the event belongs to, which is appended to the
+    /// configured event source.
+    pub fn category(&self) -> &'static str {
+        match self {
+            DomainEvent::GameCreated { .. }
+            | DomainEvent::PlayerJoined { .. }
+            | DomainEvent::PlayerLeft { .. }
+            | DomainEvent::DiceRolled { .. }
+            | DomainEvent::RoundFinished { .. }
+            | DomainEvent::NewRoundStarted { .. } => "Game",
+            DomainEvent::Disconnected { .. } => "Websocket",
+            DomainEvent::SessionExpired { .. } | DomainEvent::NicknameChanged { .. } => "Session",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialises_as_detail_type_and_detail() {
+        let event = DomainEvent::NewRoundStarted {
+            game_id: "ABCD".to_string(),
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            value,
+            json!({"detail-type": "NewRoundStarted", "detail": {"game_id": "ABCD"}})
+        );
+        assert_eq!(value["detail-type"], event.detail_type());
+    }
+}
@@ -1,4 +1,6 @@
 pub mod commands;
 pub mod default_hash_map;
 pub mod errors;
+pub mod events;
 pub mod utils;
+pub mod values;
@@ -0,0 +1,354 @@
+use crate::errors::ValidationError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
+
+macro_rules! string_value {
+    ($name:ident) => {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = ValidationError;
+
+            fn try_from(value: String) -> Result<Self, ValidationError> {
+                Self::parse(&value)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                value.0
+            }
+        }
+    };
+}
+
+string_value!(AccountId);
+string_value!(ConnectionId);
+string_value!(GameCode);
+string_value!(IdToken);
+string_value!(Nickname);
+string_value!(ReconnectToken);
+string_value!(SessionId);
+
+const MAX_ID_LENGTH: usize = 128;
+/// Generated codes are configurable within this range, so parsing accepts all of it.
+pub const GAME_CODE_LENGTH: std::ops::RangeInclusive<usize> = 4..=8;
+const MAX_ID_TOKEN_LENGTH: usize = 8192;
+const RECONNECT_TOKEN_LENGTH: usize = 43;
+const NICKNAME_LENGTH: std::ops::RangeInclusive<usize> = 2..=69;
+const RESERVED_NICKNAMES: [&str; 2] = ["MRELEVEN", "MR11"];
+const DEFAULT_PAGE_LIMIT: usize = 20;
+const MAX_PAGE_LIMIT: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum LeaderboardStat {
+    Pools,
+    WinStreak,
+    Wins,
+}
+
+impl LeaderboardStat {
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        match value {
+            "pools" => Ok(LeaderboardStat::Pools),
+            "winStreak" => Ok(LeaderboardStat::WinStreak),
+            "wins" => Ok(LeaderboardStat::Wins),
+            _ => Err(ValidationError::new(
+                "stat",
+                "Must be one of pools, winStreak or wins",
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardStat::Pools => "pools",
+            LeaderboardStat::WinStreak => "winStreak",
+            LeaderboardStat::Wins => "wins",
+        }
+    }
+}
+
+impl TryFrom<String> for LeaderboardStat {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, ValidationError> {
+        Self::parse(&value)
+    }
+}
+
+impl From<LeaderboardStat> for String {
+    fn from(value: LeaderboardStat) -> String {
+        value.as_str().to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Page {
+    pub fn parse(offset: Option<usize>, limit: Option<usize>) -> Result<Self, ValidationError> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if limit == 0 || limit > MAX_PAGE_LIMIT {
+            return Err(ValidationError::new(
+                "limit",
+                &format!("Must be between 1 and {}", MAX_PAGE_LIMIT),
+            ));
+        }
+        Ok(Page {
+            offset: offset.unwrap_or(0),
+            limit,
+        })
+    }
+}
+
+impl AccountId {
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let value = parse_identifier("accountId", value, |c| c == '-' || c == '_')?;
+        Ok(AccountId(value))
+    }
+}
+
+impl ConnectionId {
+    /// API Gateway connection IDs are base64 encoded, local ones are UUIDs.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let allowed = |c| matches!(c, '+' | '/' | '=' | '-' | '_');
+        let value = parse_identifier("connectionId", value, allowed)?;
+        Ok(ConnectionId(value))
+    }
+}
+
+impl GameCode {
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let value = value.trim().to_ascii_uppercase();
+        if !GAME_CODE_LENGTH.contains(&value.chars().count()) {
+            return Err(ValidationError::new(
+                "gameCode",
+                &format!(
+                    "Must be {} to {} characters",
+                    GAME_CODE_LENGTH.start(),
+                    GAME_CODE_LENGTH.end()
+                ),
+            ));
+        }
+        if !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(ValidationError::new(
+                "gameCode",
+                "Must only contain letters and numbers",
+            ));
+        }
+        Ok(GameCode(value))
+    }
+}
+
+impl IdToken {
+    /// Only checks the token is shaped like a JWT, the signature is verified
+    /// by the authenticator.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        if value.is_empty() || value.len() > MAX_ID_TOKEN_LENGTH {
+            return Err(ValidationError::new(
+                "token",
+                &format!("Must be between 1 and {} characters", MAX_ID_TOKEN_LENGTH),
+            ));
+        }
+        let is_base64_url = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        let segments: Vec<&str> = value.split('.').collect();
+        if segments.len() != 3 || !segments.iter().all(|s| s.chars().all(is_base64_url)) {
+            return Err(ValidationError::new("token", "Must be a JWT"));
+        }
+        Ok(IdToken(value.to_string()))
+    }
+}
+
+impl Nickname {
+    /// Normalises to NFC and collapses whitespace before checking length,
+    /// so visually identical names are stored identically.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let normalised: String = value.nfc().collect();
+        let collapsed = normalised
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if collapsed.chars().any(is_disallowed_char) {
+            return Err(ValidationError::new(
+                "nickname",
+                "Must not contain control characters",
+            ));
+        }
+        let length = collapsed.chars().count();
+        if !NICKNAME_LENGTH.contains(&length) {
+            return Err(ValidationError::new(
+                "nickname",
+                &format!(
+                    "Must be between {} and {} characters",
+                    NICKNAME_LENGTH.start(),
+                    NICKNAME_LENGTH.end()
+                ),
+            ));
+        }
+        if is_reserved_nickname(&collapsed) {
+            return Err(ValidationError::new("nickname", "Nickname is reserved"));
+        }
+        Ok(Nickname(collapsed))
+    }
+}
+
+impl ReconnectToken {
+    /// Tokens are 32 random bytes, base64 URL encoded without padding.
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let is_url_safe = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        if value.len() != RECONNECT_TOKEN_LENGTH || !value.chars().all(is_url_safe) {
+            return Err(ValidationError::new(
+                "reconnectToken",
+                "Invalid reconnect token",
+            ));
+        }
+        Ok(ReconnectToken(value.to_string()))
+    }
+}
+
+impl SessionId {
+    pub fn parse(value: &str) -> Result<Self, ValidationError> {
+        let uuid = Uuid::parse_str(value.trim())
+            .map_err(|_| ValidationError::new("sessionId", "Must be a UUID"))?;
+        Ok(SessionId(uuid.hyphenated().to_string()))
+    }
+}
+
+fn parse_identifier(
+    field: &str,
+    value: &str,
+    is_allowed_symbol: impl Fn(char) -> bool,
+) -> Result<String, ValidationError> {
+    if value.is_empty() || value.len() > MAX_ID_LENGTH {
+        return Err(ValidationError::new(
+            field,
+            &format!("Must be between 1 and {} characters", MAX_ID_LENGTH),
+        ));
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || is_allowed_symbol(c))
+    {
+        return Err(ValidationError::new(field, "Contains invalid characters"));
+    }
+    Ok(value.to_string())
+}
+
+fn is_disallowed_char(c: char) -> bool {
+    c.is_control()
+        || matches!(c, '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2060}'..='\u{2064}' | '\u{FEFF}')
+}
+
+fn is_reserved_nickname(nickname: &str) -> bool {
+    let squashed: String = nickname
+        .nfkc()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_uppercase())
+        .collect();
+    RESERVED_NICKNAMES.contains(&squashed.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nickname_counts_characters_not_bytes() {
+        assert!(Nickname::parse("日本").is_ok());
+        assert!(Nickname::parse("é").is_err());
+        assert!(Nickname::parse(&"é".repeat(69)).is_ok());
+        assert!(Nickname::parse(&"é".repeat(70)).is_err());
+    }
+
+    #[test]
+    fn nickname_is_normalised() -> Result<(), ValidationError> {
+        let decomposed = "Jose\u{0301}   Smith ";
+        let nickname = Nickname::parse(decomposed)?;
+        assert_eq!(nickname.as_str(), "Jos\u{00E9} Smith");
+        Ok(())
+    }
+
+    #[test]
+    fn nickname_rejects_control_characters() {
+        let error = Nickname::parse("ab\u{0007}").unwrap_err();
+        assert_eq!(error.field, "nickname");
+        assert!(Nickname::parse("ab\u{202E}cd").is_err());
+    }
+
+    #[test]
+    fn nickname_rejects_reserved_names() {
+        for name in ["Mr Eleven", "mr.eleven", "ＭＲ１１", "MR  11"] {
+            assert!(Nickname::parse(name).is_err(), "{name} should be reserved");
+        }
+    }
+
+    #[test]
+    fn session_id_must_be_uuid() {
+        let session_id = Uuid::new_v4().to_string();
+        assert_eq!(SessionId::parse(&session_id).unwrap().as_str(), session_id);
+        assert!(SessionId::parse("").is_err());
+        assert!(SessionId::parse("not-a-session").is_err());
+    }
+
+    #[test]
+    fn page_limit_is_bounded() {
+        assert_eq!(Page::parse(None, None).unwrap().limit, DEFAULT_PAGE_LIMIT);
+        assert!(Page::parse(Some(5), Some(MAX_PAGE_LIMIT)).is_ok());
+        assert!(Page::parse(None, Some(0)).is_err());
+        assert!(Page::parse(None, Some(MAX_PAGE_LIMIT + 1)).is_err());
+    }
+
+    #[test]
+    fn game_code_is_uppercased() {
+        assert_eq!(GameCode::parse("ab12").unwrap().as_str(), "AB12");
+        assert_eq!(GameCode::parse("ABCDE").unwrap().as_str(), "ABCDE");
+        assert!(GameCode::parse("ABC").is_err());
+        assert!(GameCode::parse("ABCDEFGHJ").is_err());
+        assert!(GameCode::parse("AB-1").is_err());
+    }
+
+    #[test]
+    fn connection_id_accepts_api_gateway_format() {
+        assert!(ConnectionId::parse("L0SM9cOFvHcCIhw=").is_ok());
+        assert!(ConnectionId::parse("").is_err());
+        assert!(ConnectionId::parse("abc def").is_err());
+    }
+
+    #[test]
+    fn deserialising_validates() {
+        let result = serde_json::from_str::<Nickname>("\"a\"");
+        assert!(result.is_err());
+        let nickname: Nickname = serde_json::from_str("\" Bob \"").unwrap();
+        assert_eq!(nickname.as_str(), "Bob");
+    }
+}
@@ -2,7 +2,7 @@ use crate::{EventMessage, IEventPublisher};
 use aws_config::{meta::region::RegionProviderChain, BehaviorVersion};
 use aws_sdk_eventbridge::{config::Region, types::PutEventsRequestEntry, Client};
 use domain::errors::LogicError;
-use std::env;
+use domain::events::DomainEvent;
 
 pub struct EventPublisher {
     client: Client,
@@ -11,7 +11,7 @@ pub struct EventPublisher {
 #[cfg_attr(feature = "in_memory", allow(unused))]
 impl EventPublisher {
     pub async fn new() -> Self {
-        let region_name = env::var("AWS_REGION").unwrap_or_else(|_| "".to_string());
+        let region_name = config::get().aws_region.clone();
         let region_provider =
             RegionProviderChain::first_try(Region::new(region_name)).or_default_provider();
         let config = aws_config::defaults(BehaviorVersion::latest())
@@ -24,7 +24,8 @@ impl EventPublisher {
 }
 
 impl IEventPublisher for EventPublisher {
-    async fn publish(&self, message: &EventMessage) -> Result<(), LogicError> {
+    async fn publish(&self, event: &DomainEvent) -> Result<(), LogicError> {
+        let message = EventMessage::new(event)?;
         let detail = serde_json::to_string(&message.detail)
             .map_err(|e| LogicError::SerializationError(e.to_string()))?;
 
@@ -43,7 +44,7 @@ impl IEventPublisher for EventPublisher {
         Ok(())
     }
 
-    fn get_messages(&self, _: &str) -> Vec<EventMessage> {
+    fn get_events(&self) -> Vec<DomainEvent> {
         vec![]
     }
 }
@@ -1,7 +1,9 @@
 use domain::errors::LogicError;
+use domain::events::DomainEvent;
 use serde::Serialize;
 use serde_json::Value;
 
+/// An event as it is put on the bus.
 #[derive(Serialize, Debug, Clone)]
 pub struct EventMessage {
     pub source: String,
@@ -9,8 +11,20 @@ pub struct EventMessage {
     pub detail: Value,
 }
 
+impl EventMessage {
+    pub fn new(event: &DomainEvent) -> Result<Self, LogicError> {
+        let mut value = serde_json::to_value(event)
+            .map_err(|e| LogicError::SerializationError(e.to_string()))?;
+        Ok(EventMessage {
+            source: format!("{}.{}", config::get().event_source, event.category()),
+            detail_type: event.detail_type().to_string(),
+            detail: value["detail"].take(),
+        })
+    }
+}
+
 #[trait_variant::make(HttpService: Send)]
 pub trait IEventPublisher {
-    async fn publish(&self, message: &EventMessage) -> Result<(), LogicError>;
-    fn get_messages(&self, source: &str) -> Vec<EventMessage>;
+    async fn publish(&self, event: &DomainEvent) -> Result<(), LogicError>;
+    fn get_events(&self) -> Vec<DomainEvent>;
 }
@@ -0,0 +1,14 @@
+mod metrics_cloud;
+mod metrics_instance;
+mod metrics_local;
+mod metrics_timer;
+mod metrics_trait;
+pub mod names;
+
+#[cfg(not(feature = "in_memory"))]
+pub use metrics_cloud::*;
+pub use metrics_instance::*;
+#[cfg(feature = "in_memory")]
+pub use metrics_local::*;
+pub use metrics_timer::*;
+pub use metrics_trait::*;
@@ -0,0 +1,54 @@
+use crate::{IMetrics, MetricDatum};
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use std::future::Future;
+
+/// Writes metrics to stdout in CloudWatch Embedded Metric Format, which
+/// Lambda forwards to CloudWatch Logs where they are extracted as metrics.
+pub struct Metrics {
+    namespace: String,
+}
+
+#[cfg_attr(feature = "in_memory", allow(unused))]
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    future.await
+}
+
+#[cfg_attr(feature = "in_memory", allow(unused))]
+impl Metrics {
+    pub async fn new() -> Self {
+        let namespace = config::get().metrics_namespace.clone();
+        Metrics { namespace }
+    }
+
+    fn to_embedded_metric_format(&self, datum: &MetricDatum) -> Value {
+        let dimension_keys: Vec<&str> = datum.dimensions.iter().map(|(k, _)| k.as_str()).collect();
+        let mut log = Map::new();
+        log.insert(
+            "_aws".to_string(),
+            json!({
+                "Timestamp": Utc::now().timestamp_millis(),
+                "CloudWatchMetrics": [{
+                    "Namespace": self.namespace,
+                    "Dimensions": [dimension_keys],
+                    "Metrics": [{"Name": datum.name, "Unit": datum.unit.as_str()}],
+                }],
+            }),
+        );
+        for (key, value) in &datum.dimensions {
+            log.insert(key.clone(), json!(value));
+        }
+        log.insert(datum.name.clone(), json!(datum.value));
+        Value::Object(log)
+    }
+}
+
+impl IMetrics for Metrics {
+    fn record(&self, datum: MetricDatum) {
+        println!("{}", self.to_embedded_metric_format(&datum));
+    }
+
+    fn get_data(&self, _name: &str) -> Vec<MetricDatum> {
+        vec![]
+    }
+}
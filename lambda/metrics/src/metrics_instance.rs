@@ -0,0 +1,14 @@
+use crate::Metrics;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+static METRICS: OnceCell<Arc<Metrics>> = OnceCell::const_new();
+
+pub async fn get() -> Arc<Metrics> {
+    METRICS.get_or_init(init).await.clone()
+}
+
+async fn init() -> Arc<Metrics> {
+    let metrics = Metrics::new().await;
+    Arc::new(metrics)
+}
@@ -0,0 +1,48 @@
+use crate::{IMetrics, MetricDatum};
+use std::future::Future;
+use std::sync::RwLock;
+
+tokio::task_local! {
+    static SCOPE: RwLock<Vec<MetricDatum>>;
+}
+
+/// Runs `future` with its own metrics log, so tests running concurrently
+/// against the shared recorder can assert exact values.
+#[cfg_attr(not(feature = "in_memory"), allow(unused))]
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    SCOPE.scope(RwLock::new(Vec::new()), future).await
+}
+
+pub struct Metrics {
+    pub log: RwLock<Vec<MetricDatum>>,
+}
+
+#[cfg_attr(not(feature = "in_memory"), allow(unused))]
+impl Metrics {
+    pub async fn new() -> Self {
+        let log = RwLock::new(Vec::new());
+        Metrics { log }
+    }
+}
+
+impl IMetrics for Metrics {
+    fn record(&self, datum: MetricDatum) {
+        if SCOPE
+            .try_with(|scope| scope.write().unwrap().push(datum.clone()))
+            .is_err()
+        {
+            self.log.write().unwrap().push(datum);
+        }
+    }
+
+    fn get_data(&self, name: &str) -> Vec<MetricDatum> {
+        let filter = |log: &RwLock<Vec<MetricDatum>>| {
+            let log = log.read().unwrap();
+            log.iter()
+                .filter(|datum| datum.name == name)
+                .cloned()
+                .collect()
+        };
+        SCOPE.try_with(filter).unwrap_or_else(|_| filter(&self.log))
+    }
+}
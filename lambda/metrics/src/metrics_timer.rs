@@ -0,0 +1,15 @@
+use crate::IMetrics;
+use std::future::Future;
+use std::time::Instant;
+
+pub async fn time<F: Future>(name: &str, operation: &str, future: F) -> F::Output {
+    let started = Instant::now();
+    let output = future.await;
+    get_and_record(name, operation, started).await;
+    output
+}
+
+async fn get_and_record(name: &str, operation: &str, started: Instant) {
+    let metrics = crate::get().await;
+    metrics.record_duration(name, &[("Operation", operation)], started.elapsed());
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Unit {
+    Count,
+    Milliseconds,
+}
+impl Unit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Unit::Count => "Count",
+            Unit::Milliseconds => "Milliseconds",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MetricDatum {
+    pub name: String,
+    pub unit: Unit,
+    pub value: f64,
+    pub dimensions: Vec<(String, String)>,
+}
+
+impl MetricDatum {
+    pub fn new(name: &str, unit: Unit, value: f64, dimensions: &[(&str, &str)]) -> Self {
+        MetricDatum {
+            name: name.to_string(),
+            unit,
+            value,
+            dimensions: dimensions
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    pub fn has_dimensions(&self, dimensions: &[(&str, &str)]) -> bool {
+        dimensions
+            .iter()
+            .all(|(key, value)| self.dimensions.iter().any(|(k, v)| k == key && v == value))
+    }
+}
+
+pub trait IMetrics {
+    fn record(&self, datum: MetricDatum);
+    fn get_data(&self, name: &str) -> Vec<MetricDatum>;
+
+    fn increment(&self, name: &str, dimensions: &[(&str, &str)]) {
+        self.record(MetricDatum::new(name, Unit::Count, 1.0, dimensions));
+    }
+
+    fn record_duration(&self, name: &str, dimensions: &[(&str, &str)], duration: Duration) {
+        let milliseconds = duration.as_secs_f64() * 1000.0;
+        self.record(MetricDatum::new(
+            name,
+            Unit::Milliseconds,
+            milliseconds,
+            dimensions,
+        ));
+    }
+
+    fn get_count(&self, name: &str, dimensions: &[(&str, &str)]) -> f64 {
+        self.get_data(name)
+            .iter()
+            .filter(|datum| datum.has_dimensions(dimensions))
+            .map(|datum| datum.value)
+            .sum()
+    }
+}
@@ -0,0 +1,8 @@
+pub const DISCONNECTS: &str = "Disconnects";
+pub const DYNAMODB_LATENCY: &str = "DynamoDbLatency";
+pub const GAMES_CREATED: &str = "GamesCreated";
+pub const NOTIFIER_LATENCY: &str = "NotifierLatency";
+pub const REQUEST_COUNT: &str = "RequestCount";
+pub const ROLL_OUTCOMES: &str = "RollOutcomes";
+pub const ROLLS: &str = "Rolls";
+pub const ROUNDS_FINISHED: &str = "RoundsFinished";
@@ -1,6 +1,10 @@
 mod models;
 mod models_for_game_state;
+mod models_for_leaderboard;
 mod models_for_nickname;
+mod models_for_profile;
+mod models_for_rate_limit;
+mod models_for_session;
 mod notifier_cloud;
 mod notifier_instance;
 mod notifier_local;
@@ -8,7 +12,11 @@ mod notifier_trait;
 
 pub use models::*;
 pub use models_for_game_state::*;
+pub use models_for_leaderboard::*;
 pub use models_for_nickname::*;
+pub use models_for_profile::*;
+pub use models_for_rate_limit::*;
+pub use models_for_session::*;
 #[cfg(not(feature = "in_memory"))]
 pub use notifier_cloud::*;
 pub use notifier_instance::*;
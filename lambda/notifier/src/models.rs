@@ -1,35 +1,54 @@
-use crate::{GameStateMessage, SetNicknameMessage};
+use crate::{
+    GameStateMessage, GetSessionMessage, LeaderboardMessage, ProfileMessage, RateLimitedMessage,
+    SetNicknameMessage,
+};
 use domain::errors::LogicError;
-use serde;
 use serde::Serialize;
+use serde_json::json;
+use std::fmt;
 
 #[derive(Serialize, Debug)]
 pub enum ActionType {
+    Authenticate(String),
     DestroySession(String),
     GameState(GameStateMessage),
-    GetSession(String),
+    GetLeaderboard(LeaderboardMessage),
+    GetProfile(ProfileMessage),
+    GetSession(GetSessionMessage),
     JoinGame(String),
+    RateLimited(RateLimitedMessage),
     SetNickname(SetNicknameMessage),
     SetNicknameFailure(String),
 }
-impl ActionType {
-    pub fn to_string(&self) -> String {
-        match self {
-            ActionType::DestroySession(_) => "destroySession".to_string(),
-            ActionType::GameState(_) => "gameState".to_string(),
-            ActionType::GetSession(_) => "getSession".to_string(),
-            ActionType::JoinGame(_) => "joinGame".to_string(),
-            ActionType::SetNickname(_) => "setNickname".to_string(),
-            ActionType::SetNicknameFailure(_) => "setNickname".to_string(),
-        }
+impl fmt::Display for ActionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ActionType::Authenticate(_) => "authenticate",
+            ActionType::DestroySession(_) => "destroySession",
+            ActionType::GameState(_) => "gameState",
+            ActionType::GetLeaderboard(_) => "getLeaderboard",
+            ActionType::GetProfile(_) => "getProfile",
+            ActionType::GetSession(_) => "getSession",
+            ActionType::JoinGame(_) => "joinGame",
+            ActionType::RateLimited(_) => "rateLimited",
+            ActionType::SetNickname(_) => "setNickname",
+            ActionType::SetNicknameFailure(_) => "setNickname",
+        };
+        write!(f, "{}", name)
     }
+}
 
+impl ActionType {
     pub fn get_value(&self) -> Result<String, LogicError> {
         match self {
+            ActionType::Authenticate(data) => Ok(data.clone()),
             ActionType::DestroySession(data) => Ok(data.clone()),
             ActionType::GameState(data) => ActionType::serialize_data(&data),
-            ActionType::GetSession(data) => Ok(data.clone()),
+            ActionType::GetLeaderboard(data) => ActionType::serialize_data(data),
+            ActionType::GetProfile(data) => ActionType::serialize_data(data),
+            ActionType::GetSession(data) => ActionType::serialize_data(data),
             ActionType::JoinGame(data) => Ok(data.clone()),
+            ActionType::RateLimited(data) => ActionType::serialize_data(data),
             ActionType::SetNickname(data) => ActionType::serialize_data(&data),
             ActionType::SetNicknameFailure(data) => Ok(data.clone()),
         }
@@ -60,4 +79,20 @@ impl Message {
             is_error: true,
         }
     }
+
+    /// The JSON text sent down the websocket.
+    pub fn render(&self) -> Result<String, LogicError> {
+        let value = if self.is_error {
+            json!({
+                "action": self.action.to_string(),
+                "error": self.action.get_value()?,
+            })
+        } else {
+            json!({
+                "action": self.action.to_string(),
+                "data": self.action.get_value()?,
+            })
+        };
+        serde_json::to_string(&value).map_err(|e| LogicError::SerializationError(e.to_string()))
+    }
 }
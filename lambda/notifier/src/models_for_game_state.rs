@@ -23,6 +23,9 @@ pub struct PlayerStateMessage {
     pub roll_result: String,
     #[serde(rename = "connectionStatus")]
     pub connection_status: String,
+    /// When a dropped player will be removed, unless they reconnect.
+    #[serde(rename = "reconnectDeadline", skip_serializing_if = "Option::is_none")]
+    pub reconnect_deadline: Option<String>,
     #[serde(rename = "rollTotal")]
     pub roll_total: i32,
     #[serde(rename = "diceValue")]
@@ -33,7 +36,7 @@ pub struct PlayerStateMessage {
 pub struct GameStateMessage {
     #[serde(rename = "gameId")]
     pub game_id: String,
-    // pub players: Vec<PlayerStateMessage>,
+    pub players: Vec<PlayerStateMessage>,
     // pub spectators: Vec<SpectatorStateMessage>,
     pub round: RoundStateMessage,
 }
@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct LeaderboardMessage {
+    pub stat: String,
+    #[serde(rename = "gameId")]
+    pub game_id: Option<String>,
+    pub entries: Vec<LeaderboardEntryMessage>,
+    #[serde(rename = "nextOffset")]
+    pub next_offset: Option<usize>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LeaderboardEntryMessage {
+    pub rank: usize,
+    #[serde(rename = "accountId")]
+    pub account_id: String,
+    pub nickname: String,
+    pub score: i32,
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Debug)]
+pub struct ProfileMessage {
+    #[serde(rename = "accountId")]
+    pub account_id: String,
+    #[serde(rename = "gamesPlayed")]
+    pub games_played: i32,
+    #[serde(rename = "longestWinStreak")]
+    pub longest_win_streak: i32,
+    #[serde(rename = "outcomeCounts")]
+    pub outcome_counts: BTreeMap<String, i32>,
+    #[serde(rename = "roundsPlayed")]
+    pub rounds_played: i32,
+    #[serde(rename = "roundsWon")]
+    pub rounds_won: i32,
+    #[serde(rename = "timesMrEleven")]
+    pub times_mr_eleven: i32,
+}
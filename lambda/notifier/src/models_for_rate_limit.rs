@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct RateLimitedMessage {
+    pub action: String,
+    #[serde(rename = "retryAfterMs")]
+    pub retry_after_ms: u64,
+}
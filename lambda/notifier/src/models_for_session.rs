@@ -0,0 +1,9 @@
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct GetSessionMessage {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+    #[serde(rename = "reconnectToken", skip_serializing_if = "Option::is_none")]
+    pub reconnect_token: Option<String>,
+}
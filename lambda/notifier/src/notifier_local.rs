@@ -1,45 +1,73 @@
 use crate::{INotifier, Message};
 use domain::errors::LogicError;
-use serde_json::json;
-use std::collections::HashMap;
+use metrics::names::NOTIFIER_LATENCY;
+use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
 
+/// Called with every message sent, so a local server can pass it on to a
+/// real socket. Returns false when the socket has gone.
+pub type Forward = Box<dyn Fn(&str, &str) -> bool + Send + Sync>;
+
 pub struct Notifier {
     pub log: RwLock<HashMap<String, Vec<String>>>,
+    closed: RwLock<HashSet<String>>,
+    forward: RwLock<Option<Forward>>,
 }
 
 #[cfg_attr(not(feature = "in_memory"), allow(unused))]
 impl Notifier {
     pub async fn new() -> Self {
         let log = RwLock::new(HashMap::new());
-        Notifier { log }
+        let closed = RwLock::new(HashSet::new());
+        let forward = RwLock::new(None);
+        Notifier {
+            log,
+            closed,
+            forward,
+        }
     }
-}
 
-impl INotifier for Notifier {
-    async fn notify(&self, connection_id: &str, message: &Message) -> Result<(), LogicError> {
-        let message_value = if message.is_error {
-            json!({
-                "action": message.action.to_string(),
-                "error": message.action.get_value()?,
-            })
-        } else {
-            json!({
-                "action": message.action.to_string(),
-                "data": message.action.get_value()?,
-            })
-        };
-        let message_string = serde_json::to_string(&message_value)
-            .map_err(|e| LogicError::SerializationError(e.to_string()))?;
+    pub fn forward_to(&self, forward: Forward) {
+        *self.forward.write().unwrap() = Some(forward);
+    }
+
+    /// Later sends to the connection fail with `ConnectionGone`, as though
+    /// the client went without a `$disconnect`.
+    pub fn close(&self, connection_id: &str) {
+        self.closed
+            .write()
+            .unwrap()
+            .insert(connection_id.to_string());
+    }
+
+    async fn send(&self, connection_id: &str, message_string: &str) -> Result<(), LogicError> {
+        if self.closed.read().unwrap().contains(connection_id) {
+            return Err(LogicError::ConnectionGone(connection_id.to_string()));
+        }
+        if let Some(forward) = self.forward.read().unwrap().as_ref() {
+            if !forward(connection_id, message_string) {
+                return Err(LogicError::ConnectionGone(connection_id.to_string()));
+            }
+        }
         let mut hash_map = self.log.write().unwrap();
         match hash_map.get_mut(connection_id) {
-            Some(log) => log.push(message_string),
+            Some(log) => log.push(message_string.to_string()),
             None => {
-                hash_map.insert(connection_id.to_string(), vec![message_string]);
+                hash_map.insert(connection_id.to_string(), vec![message_string.to_string()]);
             }
         }
         Ok(())
     }
+}
+
+impl INotifier for Notifier {
+    async fn notify(&self, connection_id: &str, message: &Message) -> Result<(), LogicError> {
+        let data = message.render()?;
+        metrics::time(NOTIFIER_LATENCY, "notify", self.send(connection_id, &data)).await
+    }
+    async fn post(&self, connection_id: &str, data: &str) -> Result<(), LogicError> {
+        metrics::time(NOTIFIER_LATENCY, "post", self.send(connection_id, data)).await
+    }
     fn get_messages(&self, connection_id: &str) -> Vec<String> {
         let hash_map = self.log.read().unwrap();
         hash_map.get(connection_id).unwrap_or(&vec![]).clone()
@@ -4,5 +4,7 @@ use domain::errors::LogicError;
 #[trait_variant::make(HttpService: Send)]
 pub trait INotifier {
     async fn notify(&self, connection_id: &str, message: &Message) -> Result<(), LogicError>;
+    /// Sends a message already rendered with `Message::render`.
+    async fn post(&self, connection_id: &str, data: &str) -> Result<(), LogicError>;
     fn get_messages(&self, connection_id: &str) -> Vec<String>;
 }
@@ -0,0 +1,11 @@
+mod scheduler_cloud;
+mod scheduler_instance;
+mod scheduler_local;
+mod scheduler_trait;
+
+#[cfg(not(feature = "in_memory"))]
+pub use scheduler_cloud::*;
+pub use scheduler_instance::*;
+#[cfg(feature = "in_memory")]
+pub use scheduler_local::*;
+pub use scheduler_trait::*;
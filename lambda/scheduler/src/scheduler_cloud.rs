@@ -0,0 +1,52 @@
+use crate::{IScheduler, ScheduledJob};
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion};
+use aws_sdk_eventbridge::{config::Region, types::PutEventsRequestEntry, Client};
+use domain::errors::LogicError;
+
+pub const DETAIL_TYPE: &str = "CommandScheduled";
+
+// Jobs go through EventBridge to an SQS queue with a fixed delay, and
+// api_sqs_entry puts back any that aren't due yet
+pub struct Scheduler {
+    client: Client,
+}
+
+#[cfg_attr(feature = "in_memory", allow(unused))]
+impl Scheduler {
+    pub async fn new() -> Self {
+        let region_name = config::get().aws_region.clone();
+        let region_provider =
+            RegionProviderChain::first_try(Region::new(region_name)).or_default_provider();
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .region(region_provider)
+            .load()
+            .await;
+        let client = Client::new(&config);
+        Scheduler { client }
+    }
+}
+
+impl IScheduler for Scheduler {
+    async fn schedule(&self, job: &ScheduledJob) -> Result<(), LogicError> {
+        let detail = serde_json::to_string(job)
+            .map_err(|e| LogicError::SerializationError(e.to_string()))?;
+
+        let entry = PutEventsRequestEntry::builder()
+            .detail_type(DETAIL_TYPE)
+            .detail(&detail)
+            .source(format!("{}.Scheduler", config::get().event_source))
+            .build();
+
+        self.client
+            .put_events()
+            .entries(entry)
+            .send()
+            .await
+            .map_err(|e| LogicError::EventPublishingError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn get_jobs(&self) -> Vec<ScheduledJob> {
+        vec![]
+    }
+}
@@ -0,0 +1,14 @@
+use crate::Scheduler;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+static SCHEDULER: OnceCell<Arc<Scheduler>> = OnceCell::const_new();
+
+pub async fn get() -> Arc<Scheduler> {
+    SCHEDULER.get_or_init(init).await.clone()
+}
+
+async fn init() -> Arc<Scheduler> {
+    let scheduler = Scheduler::new().await;
+    Arc::new(scheduler)
+}
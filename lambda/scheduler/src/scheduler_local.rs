@@ -0,0 +1,52 @@
+use crate::{IScheduler, ScheduledJob};
+use chrono::Utc;
+use domain::{commands::ScheduledCommand, errors::LogicError};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+
+pub type Run =
+    Arc<dyn Fn(ScheduledCommand) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+pub struct Scheduler {
+    pub log: RwLock<Vec<ScheduledJob>>,
+    run: RwLock<Option<Run>>,
+}
+
+#[cfg_attr(not(feature = "in_memory"), allow(unused))]
+impl Scheduler {
+    pub async fn new() -> Self {
+        let log = RwLock::new(Vec::new());
+        let run = RwLock::new(None);
+        Scheduler { log, run }
+    }
+
+    /// Run each job on a timer in this process. Until this is called jobs
+    /// are only logged.
+    pub fn run_with(&self, run: Run) {
+        *self.run.write().unwrap() = Some(run);
+    }
+}
+
+impl IScheduler for Scheduler {
+    async fn schedule(&self, job: &ScheduledJob) -> Result<(), LogicError> {
+        self.log.write().unwrap().push(job.clone());
+
+        let run = self.run.read().unwrap().clone();
+        if let Some(run) = run {
+            let delay = (job.run_at - Utc::now()).to_std().unwrap_or_default();
+            let command = job.command.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                run(command).await;
+            });
+        }
+        Ok(())
+    }
+
+    fn get_jobs(&self) -> Vec<ScheduledJob> {
+        self.log.read().unwrap().clone()
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use domain::{commands::ScheduledCommand, errors::LogicError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScheduledJob {
+    pub command: ScheduledCommand,
+    pub run_at: DateTime<Utc>,
+}
+
+impl ScheduledJob {
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.run_at <= now
+    }
+}
+
+#[trait_variant::make(HttpService: Send)]
+pub trait IScheduler {
+    async fn schedule(&self, job: &ScheduledJob) -> Result<(), LogicError>;
+    fn get_jobs(&self) -> Vec<ScheduledJob>;
+}
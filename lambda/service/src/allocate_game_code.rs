@@ -0,0 +1,25 @@
+use domain::errors::LogicError;
+use storage::game_table::GameItem;
+use storage::DynamoDbClient;
+
+// With 32^4 codes, running out of attempts means the codes are nearly all
+// taken and the length should be raised
+const MAX_ATTEMPTS: usize = 5;
+
+/// Picks a code that no game holds. An abandoned game's code is only free
+/// again once the sweeper has torn the game down and told its players.
+/// Another game may still take it before we save, which the save's condition
+/// catches.
+pub async fn allocate(db: &DynamoDbClient) -> Result<String, LogicError> {
+    for _ in 0..MAX_ATTEMPTS {
+        let game_code = GameItem::create_game_code();
+        match GameItem::from_db(&game_code, db).await {
+            Err(LogicError::ItemNotFound(_)) => return Ok(game_code),
+            Ok(_) => println!("Game code {game_code} is taken"),
+            Err(e) => return Err(e),
+        }
+    }
+    Err(LogicError::ConditionalCheckFailed(
+        "No free game code found".to_string(),
+    ))
+}
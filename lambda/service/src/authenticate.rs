@@ -0,0 +1,28 @@
+use crate::authorize;
+use auth::{self, IAuthenticator};
+use chrono::Utc;
+use domain::commands::AuthenticateCommand;
+use domain::errors::LogicError;
+use notifier::{self, ActionType, INotifier, Message};
+use storage::session_table::SessionAction;
+use storage::IDynamoDbClient;
+
+pub async fn handler(command: &AuthenticateCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let authenticator = auth::get().await;
+    let notifier = notifier::get().await;
+
+    let mut session =
+        authorize::get_owned_session(&command.connection_id, &command.session_id, &db).await?;
+    let claims = authenticator.verify(&command.token).await?;
+
+    session.account_id = Some(claims.sub.clone());
+    session.modified_action = SessionAction::Authenticated;
+    session.modified_at = Utc::now();
+    session.version += 1;
+    db.write_single(session.save()?).await?;
+
+    let message = Message::new(ActionType::Authenticate(claims.sub.clone()));
+    notifier.notify(&command.connection_id, &message).await?;
+    Ok(claims.sub)
+}
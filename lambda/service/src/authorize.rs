@@ -0,0 +1,53 @@
+use domain::errors::LogicError;
+use storage::game_table::GameItem;
+use storage::session_table::SessionItem;
+use storage::websocket_table::WebsocketItem;
+use storage::DynamoDbClient;
+
+/// Resolves the session bound to the calling connection. The session ID sent
+/// by the client must match it, so knowing a session ID is not enough to act
+/// as that player.
+pub async fn get_owned_session(
+    connection_id: &str,
+    session_id: &str,
+    db: &DynamoDbClient,
+) -> Result<SessionItem, LogicError> {
+    let connection = WebsocketItem::from_db(connection_id, db).await?;
+    if connection.session_id.as_deref() != Some(session_id) {
+        println!("Connection is not bound to session");
+        return Err(LogicError::NotAllowed);
+    }
+    SessionItem::from_db(session_id, db).await
+}
+
+/// REST callers are identified by account, so they can only read games they
+/// are playing in.
+pub async fn get_account_game(
+    account_id: &str,
+    game_id: &str,
+    db: &DynamoDbClient,
+) -> Result<GameItem, LogicError> {
+    let game = GameItem::from_db(game_id, db).await?;
+    let is_player = game
+        .players
+        .iter()
+        .any(|p| p.account_id.as_deref() == Some(account_id));
+    if !is_player {
+        println!("Account is not in game");
+        return Err(LogicError::NotAllowed);
+    }
+    Ok(game)
+}
+
+pub async fn get_account_session(
+    account_id: &str,
+    session_id: &str,
+    db: &DynamoDbClient,
+) -> Result<SessionItem, LogicError> {
+    let session = SessionItem::from_db(session_id, db).await?;
+    if session.account_id.as_deref() != Some(account_id) {
+        println!("Session is not linked to account");
+        return Err(LogicError::NotAllowed);
+    }
+    Ok(session)
+}
@@ -4,13 +4,16 @@ use domain::commands::{CheckSessionTimeoutCommand, DestroySessionCommand};
 use domain::errors::LogicError;
 use storage::session_table::{SessionAction, SessionItem};
 
+/// How long a disconnected session is held for the player to reconnect.
+pub const RECONNECT_GRACE_SECS: i64 = 30;
+
 pub async fn handler(command: &CheckSessionTimeoutCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
 
     let session = SessionItem::from_db(&command.session_id, &db).await;
     let session = match session {
         Ok(session) => session,
-        Err(LogicError::GetItemError(_)) => return Ok("Session already deleted".to_string()),
+        Err(LogicError::ItemNotFound(_)) => return Ok("Session already deleted".to_string()),
         Err(e) => return Err(e),
     };
     if session.modified_action != SessionAction::PendingTimeout {
@@ -18,8 +21,7 @@ pub async fn handler(command: &CheckSessionTimeoutCommand) -> Result<String, Log
     }
     let now = Utc::now();
     let seconds_since_disconnected = (now - session.modified_at).num_seconds();
-    let seconds_timeout = 30;
-    if seconds_since_disconnected < seconds_timeout {
+    if seconds_since_disconnected < RECONNECT_GRACE_SECS {
         return Ok("Session is not timed out".to_string());
     }
 
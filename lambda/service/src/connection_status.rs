@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use domain::errors::LogicError;
+use storage::game_table::{GameAction, GameItem};
+use storage::IDynamoDbClient;
+
+// The game is busy while players roll, so give it a few goes
+const MAX_ATTEMPTS: usize = 3;
+
+/// Marks the player as dropped until `reconnect_deadline`, or as connected
+/// when it's None. The other players hear about it from the game stream.
+pub async fn update(
+    game_id: &str,
+    session_id: &str,
+    reconnect_deadline: Option<DateTime<Utc>>,
+) -> Result<(), LogicError> {
+    let db = storage::get().await;
+    let action = match reconnect_deadline {
+        Some(_) => GameAction::Disconnect,
+        None => GameAction::Reconnect,
+    };
+    for _ in 0..MAX_ATTEMPTS {
+        let mut game = match GameItem::from_db(game_id, &db).await {
+            Err(LogicError::ItemNotFound(_)) => return Ok(()),
+            result => result?,
+        };
+        let Some(player) = game.players.iter_mut().find(|p| p.player_id == session_id) else {
+            return Ok(());
+        };
+        if player.reconnect_deadline == reconnect_deadline {
+            return Ok(());
+        }
+        player.reconnect_deadline = reconnect_deadline;
+        game.version += 1;
+        game.modified_action = action.clone();
+        game.modified_at = Utc::now();
+        game.modified_by = session_id.to_string();
+        match db.write_single(game.save()?).await {
+            Err(LogicError::ConditionalCheckFailed(_)) => continue,
+            result => return result,
+        }
+    }
+    Err(LogicError::ConditionalCheckFailed(
+        "Max retries reached".to_string(),
+    ))
+}
@@ -1,53 +1,77 @@
-use crate::send_game_state_notification;
+use crate::{allocate_game_code, authorize, outbox, update_profiles};
 use chrono::Utc;
-use domain::commands::{CreateGameCommand, SendGameStateNotificationCommand};
+use domain::commands::CreateGameCommand;
 use domain::errors::LogicError;
-use event_publisher::{self, EventMessage, IEventPublisher};
-use notifier::{self, ActionType, INotifier, Message};
-use serde_json::json;
+use domain::events::DomainEvent;
+use metrics::names::GAMES_CREATED;
+use metrics::IMetrics;
+use notifier::{ActionType, Message};
 use storage::game_table::{GameItem, PlayerItem};
 use storage::session_table::{SessionAction, SessionItem};
-use storage::IDynamoDbClient;
+use storage::{DynamoDbClient, IDynamoDbClient};
 
 pub async fn handler(command: &CreateGameCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
-    let event_publisher = event_publisher::get().await;
-    let notifier = notifier::get().await;
+    let metrics = metrics::get().await;
 
-    let mut session = SessionItem::from_db(&command.session_id, &db).await?;
+    let session =
+        authorize::get_owned_session(&command.connection_id, &command.session_id, &db).await?;
     let nickname = session.nickname.clone().ok_or(LogicError::NotAllowed)?;
     if session.game_id.is_some() {
         return Ok("Already in game".to_string());
     }
 
-    let game_id = GameItem::create_game_code();
-    let mut game = GameItem::new(&game_id, &command.session_id);
-    let player = PlayerItem::new(&session.session_id, &session.account_id, &nickname);
-    game.players.push(player);
-    session.game_id = Some(game_id.clone());
-    session.modified_action = SessionAction::JoinGame;
-    session.modified_at = Utc::now();
-    session.version += 1;
-
-    db.write(vec![game.save()?, session.save()?]).await?;
-
-    println!("Sending new game response");
-    let message = Message::new(ActionType::JoinGame(game_id.clone()));
-    notifier.notify(&command.connection_id, &message).await?;
+    let game_id = create(&session, &nickname, &db).await?;
+    metrics.increment(GAMES_CREATED, &[]);
+    Ok(game_id)
+}
 
-    println!("Sending game state notification");
-    let command = SendGameStateNotificationCommand {
-        game_id: game_id.clone(),
-    };
-    send_game_state_notification::handler(&command).await?;
+// Losing a code to a concurrent game is rare, so a few fresh codes is plenty
+const MAX_ATTEMPTS: usize = 3;
 
-    println!("Sending event message");
-    let event_message = EventMessage {
-        source: "RustLambda-Dev.GameCreated".to_string(),
-        detail_type: "Game created".to_string(),
-        detail: json!({"game_id": game_id}),
-    };
-    event_publisher.publish(&event_message).await?;
+/// Saves the game under a newly allocated code, trying another code if
+/// someone else takes it first.
+async fn create(
+    session: &SessionItem,
+    nickname: &str,
+    db: &DynamoDbClient,
+) -> Result<String, LogicError> {
+    for _ in 0..MAX_ATTEMPTS {
+        let game_id = allocate_game_code::allocate(db).await?;
+        let mut game = GameItem::new(&game_id, &session.session_id);
+        let player = PlayerItem::new(&session.session_id, &session.account_id, nickname);
+        game.players.push(player);
+        let mut session = session.clone();
+        session.game_id = Some(game_id.clone());
+        session.modified_action = SessionAction::JoinGame;
+        session.modified_at = Utc::now();
+        session.version += 1;
 
-    Ok(game_id)
+        let message = Message::new(ActionType::JoinGame(game_id.clone()));
+        let mut transactions = vec![
+            game.save()?,
+            session.save()?,
+            outbox::notification(&session.connection_id, &message)?,
+            outbox::event(DomainEvent::GameCreated {
+                game_id: game_id.clone(),
+                session_id: session.session_id.clone(),
+            })?,
+            outbox::event(DomainEvent::PlayerJoined {
+                game_id: game_id.clone(),
+                session_id: session.session_id.clone(),
+                nickname: nickname.to_string(),
+            })?,
+        ];
+        if let Some(ref account_id) = session.account_id {
+            let profile = update_profiles::game_created(account_id, db).await?;
+            transactions.push(profile.save()?);
+        }
+        match db.write(transactions).await {
+            Err(LogicError::ConditionalCheckFailed(_)) => continue,
+            result => return result.map(|_| game_id),
+        }
+    }
+    Err(LogicError::ConditionalCheckFailed(
+        "Max retries reached".to_string(),
+    ))
 }
@@ -1,7 +1,8 @@
+use crate::reconnect_token;
 use chrono::Utc;
 use domain::commands::CreateSessionCommand;
 use domain::errors::LogicError;
-use notifier::{self, ActionType, INotifier, Message};
+use notifier::{self, ActionType, GetSessionMessage, INotifier, Message};
 use storage::session_table::SessionItem;
 use storage::websocket_table::WebsocketItem;
 use storage::IDynamoDbClient;
@@ -12,20 +13,31 @@ pub async fn handler(command: &CreateSessionCommand) -> Result<String, LogicErro
     let notifier = notifier::get().await;
 
     let mut connection = WebsocketItem::from_db(&command.connection_id, &db).await?;
-    let session_id = match connection.session_id {
-        Some(session_id) => session_id,
+    let (session_id, reconnect_token) = match connection.session_id {
+        Some(session_id) => (session_id, None),
         None => {
             let session_id = Uuid::new_v4().to_string();
-            let session = SessionItem::new(&session_id, &command.connection_id);
+            let token = reconnect_token::generate();
+            let mut session = SessionItem::new(&session_id, &command.connection_id);
+            session.reconnect_token_hash = Some(reconnect_token::hash(&token));
             connection.session_id = Some(session_id.clone());
             connection.version += 1;
             connection.modified_at = Utc::now();
             db.write(vec![session.save()?, connection.save()?]).await?;
-            session_id
+            (session_id, Some(token))
         }
     };
 
-    let message = Message::new(ActionType::GetSession(session_id.clone()));
-    notifier.notify(&connection.connection_id, &message).await?;
+    let message = Message::new(ActionType::GetSession(GetSessionMessage {
+        session_id: session_id.clone(),
+        reconnect_token,
+    }));
+    // The session is saved either way, so this is not worth failing over
+    if let Err(e) = notifier.notify(&connection.connection_id, &message).await {
+        println!(
+            "Failed to send session to {}: {e}",
+            connection.connection_id
+        );
+    }
     Ok(session_id)
 }
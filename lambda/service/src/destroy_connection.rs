@@ -1,36 +1,60 @@
-use chrono::Utc;
-use domain::commands::DestroyConnectionCommand;
+use crate::check_session_timeout::RECONNECT_GRACE_SECS;
+use crate::{connection_status, outbox};
+use chrono::{TimeDelta, Utc};
+use domain::commands::{CheckSessionTimeoutCommand, DestroyConnectionCommand, ScheduledCommand};
 use domain::errors::LogicError;
-use event_publisher::{self, EventMessage, IEventPublisher};
-use serde_json::json;
+use domain::events::DomainEvent;
+use metrics::names::DISCONNECTS;
+use metrics::IMetrics;
+use scheduler::ScheduledJob;
 use storage::session_table::{SessionAction, SessionItem};
 use storage::websocket_table::WebsocketItem;
 use storage::IDynamoDbClient;
 
 pub async fn handler(command: &DestroyConnectionCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
-    let event_publisher = event_publisher::get().await;
+    let metrics = metrics::get().await;
 
     let connection = WebsocketItem::from_db(&command.connection_id, &db).await?;
     match &connection.session_id {
         Some(session_id) => {
-            let mut session = SessionItem::from_db(&session_id, &db).await?;
+            let mut session = SessionItem::from_db(session_id, &db).await?;
             session.modified_action = SessionAction::PendingTimeout;
             session.modified_at = Utc::now();
             session.version += 1;
-            db.write(vec![connection.delete()?, session.save()?])
-                .await?;
-
-            let event_message = EventMessage {
-                source: "RustLambda-Dev.Websocket".to_string(),
-                detail_type: "Disconnected".to_string(),
-                detail: json!({"session_id": session_id}),
+            let event = DomainEvent::Disconnected {
+                session_id: session_id.clone(),
+            };
+            let deadline = session.modified_at + TimeDelta::seconds(RECONNECT_GRACE_SECS);
+            // Scheduled through the outbox, so the session can't be left
+            // pending with nothing to time it out
+            let job = ScheduledJob {
+                command: ScheduledCommand::CheckSessionTimeout(CheckSessionTimeoutCommand {
+                    session_id: session_id.clone(),
+                }),
+                run_at: deadline,
             };
-            event_publisher.publish(&event_message).await?;
+            db.write(vec![
+                connection.delete()?,
+                session.save()?,
+                outbox::event(event)?,
+                outbox::schedule(&job)?,
+            ])
+            .await?;
+
+            // The connection is already gone, so a retry couldn't get this
+            // far. Players still see the drop once the session times out.
+            if let Some(game_id) = &session.game_id {
+                let result = connection_status::update(game_id, session_id, Some(deadline)).await;
+                if let Err(e) = result {
+                    println!("Failed to mark {session_id} as dropped from {game_id}: {e}");
+                }
+            }
         }
         None => {
             db.write_single(connection.delete()?).await?;
         }
     }
+    metrics.increment(DISCONNECTS, &[]);
     Ok("Success".to_string())
 }
@@ -1,15 +1,15 @@
 use chrono::Utc;
 use domain::commands::{DestroySessionCommand, LeaveGameCommand};
 use domain::errors::LogicError;
-use notifier::{self, ActionType, INotifier, Message};
+use domain::events::DomainEvent;
+use notifier::{ActionType, Message};
 use storage::session_table::SessionItem;
 use storage::IDynamoDbClient;
 
-use crate::leave_game;
+use crate::{leave_game, outbox};
 
 pub async fn handler(command: &DestroySessionCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
-    let notifier = notifier::get().await;
 
     let mut session = SessionItem::from_db(&command.session_id, &db).await?;
     session.version += 1;
@@ -20,14 +20,21 @@ pub async fn handler(command: &DestroySessionCommand) -> Result<String, LogicErr
             game_id: game_id.clone(),
             session_id: session.session_id.clone(),
         };
-        leave_game::handler(&request).await?;
+        match leave_game::handler(&request).await {
+            // Nothing to leave if the game was already torn down
+            Err(LogicError::ItemNotFound(_)) => println!("Game {game_id} already deleted"),
+            result => result.map(|_| ())?,
+        }
     }
 
-    db.write_single(session.delete()?).await?;
-
+    let event = DomainEvent::SessionExpired {
+        session_id: command.session_id.clone(),
+    };
+    let mut transactions = vec![session.delete()?, outbox::event(event)?];
     if let Some(connection_id) = &command.connection_id {
         let message = Message::new(ActionType::DestroySession(command.session_id.clone()));
-        notifier.notify(&connection_id, &message).await?;
+        transactions.push(outbox::notification(connection_id, &message)?);
     }
+    db.write(transactions).await?;
     Ok("Success".to_string())
 }
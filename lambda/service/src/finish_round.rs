@@ -1,3 +1,5 @@
+#![allow(clippy::needless_borrow, clippy::redundant_field_names)]
+
 use domain::errors::LogicError;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -0,0 +1,31 @@
+use crate::send_game_state_notification;
+use aws_sdk_dynamodb::types::AttributeValue;
+use domain::errors::LogicError;
+use std::collections::HashMap;
+use storage::game_table::GameItem;
+use storage::stream_record::StreamRecord;
+
+/// Sends a game to its players each time a change to it commits. Runs from
+/// the game table's stream in AWS, and on every write to an in memory table.
+pub async fn handler(record: &StreamRecord) -> Result<String, LogicError> {
+    let old_game = parse(&record.old_image)?;
+    let Some(game) = parse(&record.new_image)? else {
+        // Deleted once the last player left, there's nobody to tell
+        return Ok("Skipped".to_string());
+    };
+    if old_game.is_some_and(|old_game| old_game.version == game.version) {
+        return Ok("Unchanged".to_string());
+    }
+    // Players who missed out are sent the game with the next change, or when
+    // they reconnect, so failures aren't retried
+    let recipients = send_game_state_notification::send(&game).await?;
+    serde_json::to_string(&recipients).map_err(|e| LogicError::SerializationError(e.to_string()))
+}
+
+// Sessions share the game table, they don't have players
+fn parse(image: &Option<HashMap<String, AttributeValue>>) -> Result<Option<GameItem>, LogicError> {
+    match image {
+        Some(image) if image.contains_key("players") => GameItem::from_map(image).map(Some),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,69 @@
+use domain::commands::{GetLeaderboardCommand, QueryLeaderboardCommand};
+use domain::errors::LogicError;
+use domain::values::{GameCode, LeaderboardStat, Page};
+use notifier::{self, ActionType, INotifier, LeaderboardEntryMessage, LeaderboardMessage, Message};
+use storage::game_table::GameItem;
+use storage::leaderboard_table::{LeaderboardItem, LeaderboardScope};
+use storage::DynamoDbClient;
+
+pub async fn handler(command: &GetLeaderboardCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let notifier = notifier::get().await;
+
+    let leaderboard = build_message(command.stat, &command.game_id, &command.page, &db).await?;
+    let message = Message::new(ActionType::GetLeaderboard(leaderboard));
+    notifier.notify(&command.connection_id, &message).await?;
+    Ok("Success".to_string())
+}
+
+pub async fn query(command: &QueryLeaderboardCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let leaderboard = build_message(command.stat, &command.game_id, &command.page, &db).await?;
+    serde_json::to_string(&leaderboard).map_err(|e| LogicError::SerializationError(e.to_string()))
+}
+
+async fn build_message(
+    stat: LeaderboardStat,
+    game_id: &Option<GameCode>,
+    page: &Page,
+    db: &DynamoDbClient,
+) -> Result<LeaderboardMessage, LogicError> {
+    let scope = match game_id {
+        Some(game_id) => match GameItem::from_db(game_id.as_str(), db).await {
+            Ok(game) => Some(LeaderboardScope::Group(game.instance_id)),
+            // A group board is only reachable while its game is
+            Err(LogicError::ItemNotFound(_)) => None,
+            Err(e) => return Err(e),
+        },
+        None => Some(LeaderboardScope::Global),
+    };
+    let board = match scope {
+        Some(scope) => LeaderboardItem::top(&LeaderboardItem::board_id(&scope, stat), db).await?,
+        None => vec![],
+    };
+
+    let start = page.offset.min(board.len());
+    let end = start.saturating_add(page.limit).min(board.len());
+    let entries: Vec<LeaderboardEntryMessage> = board[start..end]
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| LeaderboardEntryMessage {
+            rank: page.offset + i + 1,
+            account_id: entry.account_id.clone(),
+            nickname: entry.nickname.clone(),
+            score: entry.score,
+        })
+        .collect();
+    let next_offset = page.offset + entries.len();
+    let next_offset = if next_offset < board.len() {
+        Some(next_offset)
+    } else {
+        None
+    };
+    Ok(LeaderboardMessage {
+        stat: stat.as_str().to_string(),
+        game_id: game_id.as_ref().map(|g| g.to_string()),
+        entries,
+        next_offset,
+    })
+}
@@ -0,0 +1,38 @@
+use crate::authorize;
+use domain::commands::{GetAccountProfileCommand, GetProfileCommand};
+use domain::errors::LogicError;
+use notifier::{self, ActionType, INotifier, Message, ProfileMessage};
+use storage::profile_table::ProfileItem;
+
+pub async fn handler(command: &GetProfileCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let notifier = notifier::get().await;
+
+    let session =
+        authorize::get_owned_session(&command.connection_id, &command.session_id, &db).await?;
+    let account_id = session.account_id.ok_or(LogicError::NotAllowed)?;
+    let profile = ProfileItem::from_db_or_new(&account_id, &db).await?;
+
+    let message = Message::new(ActionType::GetProfile(to_message(&profile)));
+    notifier.notify(&command.connection_id, &message).await?;
+    Ok("Success".to_string())
+}
+
+pub async fn get_for_account(command: &GetAccountProfileCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let profile = ProfileItem::from_db_or_new(&command.account_id, &db).await?;
+    serde_json::to_string(&to_message(&profile))
+        .map_err(|e| LogicError::SerializationError(e.to_string()))
+}
+
+pub(crate) fn to_message(profile: &ProfileItem) -> ProfileMessage {
+    ProfileMessage {
+        account_id: profile.account_id.clone(),
+        games_played: profile.games_played,
+        longest_win_streak: profile.longest_win_streak,
+        outcome_counts: profile.outcome_counts.clone(),
+        rounds_played: profile.rounds_played,
+        rounds_won: profile.rounds_won,
+        times_mr_eleven: profile.times_mr_eleven,
+    }
+}
@@ -0,0 +1,128 @@
+use domain::errors::LogicError;
+use serde::Serialize;
+use std::future::Future;
+use std::time::Instant;
+use storage::game_table::GameItem;
+use storage::leaderboard_table::LeaderboardItem;
+use storage::outbox_table::OutboxItem;
+use storage::profile_table::ProfileItem;
+use storage::rate_limit_table::RateLimitItem;
+use storage::websocket_table::WebsocketItem;
+
+// No item has this key, so a reachable table answers ItemNotFound
+const PROBE_KEY: &str = "health-check";
+
+/// The report, and whether every dependency could be reached.
+pub struct Health {
+    pub healthy: bool,
+    pub body: String,
+}
+
+#[derive(Serialize)]
+struct HealthView {
+    status: &'static str,
+    config: ConfigView,
+    dependencies: Vec<DependencyView>,
+}
+
+/// The running config, leaving out the Cognito ids and the local account.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigView {
+    aws_region: String,
+    event_source: String,
+    metrics_namespace: String,
+    game_code_length: usize,
+    connection_ttl_secs: u64,
+    game_code_ttl_secs: u64,
+    session_ttl_secs: u64,
+    tables: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct DependencyView {
+    name: String,
+    reachable: bool,
+    #[serde(rename = "latencyMs")]
+    latency_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Reports the running config and whether each table can be read. The
+/// report is degraded rather than an error if any can't.
+pub async fn handler() -> Result<Health, LogicError> {
+    let config = config::get();
+    let db = storage::get().await;
+    let dependencies = vec![
+        probe(&config.game_table_name, GameItem::from_db(PROBE_KEY, &db)).await,
+        probe(
+            &config.leaderboard_table_name,
+            LeaderboardItem::from_db(PROBE_KEY, PROBE_KEY, &db),
+        )
+        .await,
+        probe(
+            &config.outbox_table_name,
+            OutboxItem::from_db(PROBE_KEY, &db),
+        )
+        .await,
+        probe(
+            &config.profile_table_name,
+            ProfileItem::from_db(PROBE_KEY, &db),
+        )
+        .await,
+        probe(
+            &config.rate_limit_table_name,
+            RateLimitItem::from_db_or_new(PROBE_KEY, 0, &db),
+        )
+        .await,
+        probe(
+            &config.websocket_table_name,
+            WebsocketItem::from_db(PROBE_KEY, &db),
+        )
+        .await,
+    ];
+    let healthy = dependencies.iter().all(|d| d.reachable);
+    let view = HealthView {
+        status: if healthy { "ok" } else { "degraded" },
+        config: ConfigView {
+            aws_region: config.aws_region.clone(),
+            event_source: config.event_source.clone(),
+            metrics_namespace: config.metrics_namespace.clone(),
+            game_code_length: config.game_code_length,
+            connection_ttl_secs: config.connection_ttl_secs,
+            game_code_ttl_secs: config.game_code_ttl_secs,
+            session_ttl_secs: config.session_ttl_secs,
+            tables: vec![
+                config.game_table_name.clone(),
+                config.leaderboard_table_name.clone(),
+                config.name_table_name.clone(),
+                config.outbox_table_name.clone(),
+                config.profile_table_name.clone(),
+                config.rate_limit_table_name.clone(),
+                config.websocket_table_name.clone(),
+            ],
+        },
+        dependencies,
+    };
+    let body =
+        serde_json::to_string(&view).map_err(|e| LogicError::SerializationError(e.to_string()))?;
+    Ok(Health { healthy, body })
+}
+
+async fn probe<T>(
+    table_name: &str,
+    read: impl Future<Output = Result<T, LogicError>>,
+) -> DependencyView {
+    let start = Instant::now();
+    let error = match read.await {
+        Ok(_) | Err(LogicError::ItemNotFound(_)) => None,
+        Err(e) => Some(e.to_string()),
+    };
+    DependencyView {
+        name: format!("dynamodb:{table_name}"),
+        reachable: error.is_none(),
+        latency_ms: start.elapsed().as_millis(),
+        error,
+    }
+}
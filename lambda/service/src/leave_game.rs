@@ -1,11 +1,11 @@
 use chrono::Utc;
-use domain::commands::{LeaveGameCommand, SendGameStateNotificationCommand};
+use domain::commands::LeaveGameCommand;
 use domain::errors::LogicError;
-use game_logic::finish_round;
+use domain::events::DomainEvent;
 use storage::game_table::{GameAction, GameItem};
 use storage::IDynamoDbClient;
 
-use crate::send_game_state_notification;
+use crate::{finish_round, outbox, record_metrics, update_leaderboards, update_profiles};
 
 pub async fn handler(command: &LeaveGameCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
@@ -15,23 +15,44 @@ pub async fn handler(command: &LeaveGameCommand) -> Result<String, LogicError> {
     game.version += 1;
     game.modified_at = Utc::now();
     game.modified_action = GameAction::LeaveGame;
+    let event = DomainEvent::PlayerLeft {
+        game_id: command.game_id.clone(),
+        session_id: command.session_id.clone(),
+    };
 
     if game.players.is_empty() {
         println!("No more players");
-        db.write_single(game.delete()?).await?;
+        db.write(vec![game.delete()?, outbox::event(event)?])
+            .await?;
         return Ok("Success".to_string());
     }
-    if game.players.iter().all(|p| p.finished) {
+    let previous_mr_eleven = game.mr_eleven.clone();
+    let is_round_finished = game.players.iter().all(|p| p.finished);
+    let mut round = None;
+    if is_round_finished {
         println!("All players finished");
-        game = finish_round::finish_round(&mut game)?;
+        finish_round::handler(&mut game)?;
+        if game.round_finished {
+            round = Some(game.record_round());
+        }
     }
 
-    db.write_single(game.save()?).await?;
-
-    let command = SendGameStateNotificationCommand {
-        game_id: game.game_id.clone(),
-    };
-    send_game_state_notification::handler(&command).await?;
+    let mut transactions = vec![outbox::event(event)?];
+    if let Some(round) = round {
+        transactions.push(round.save()?);
+        transactions.push(outbox::round_finished(&game)?);
+        let profiles = update_profiles::round_finished(&game, &previous_mr_eleven, &db).await?;
+        let leaderboards = update_leaderboards::round_finished(&mut game, &profiles);
+        transactions.extend(outbox::leaderboard(leaderboards)?);
+        for profile in profiles {
+            transactions.push(profile.save()?);
+        }
+    }
+    transactions.push(game.save()?);
+    db.write(transactions).await?;
+    if is_round_finished && game.round_finished {
+        record_metrics::round_finished(&game).await;
+    }
 
     Ok("Success".to_string())
 }
@@ -1,15 +1,34 @@
+pub mod allocate_game_code;
+pub mod authenticate;
+pub mod authorize;
 pub mod check_session_timeout;
+mod connection_status;
 pub mod create_connection;
 pub mod create_game;
 pub mod create_session;
 pub mod destroy_connection;
 pub mod destroy_session;
 pub mod finish_round;
+pub mod game_stream;
+pub mod get_leaderboard;
+pub mod get_profile;
 pub mod goodbye;
+pub mod health;
 pub mod hello;
 pub mod leave_game;
 pub mod new_round;
+mod outbox;
+pub mod reconnect_token;
+pub mod relay_outbox;
+mod record_metrics;
 pub mod roll_dice;
+pub mod run_scheduled_command;
 pub mod send_game_state_notification;
 pub mod set_nickname;
 pub mod set_session;
+pub mod sweep;
+mod update_leaderboards;
+mod update_profiles;
+pub mod view_account;
+pub mod view_game;
+pub mod view_session;
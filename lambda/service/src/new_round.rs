@@ -1,15 +1,16 @@
-use crate::send_game_state_notification;
+use crate::{authorize, outbox};
 use chrono::Utc;
-use domain::commands::{NewRoundCommand, SendGameStateNotificationCommand};
+use domain::commands::NewRoundCommand;
 use domain::errors::LogicError;
+use domain::events::DomainEvent;
 use storage::game_table::{GameAction, GameItem};
-use storage::session_table::SessionItem;
 use storage::IDynamoDbClient;
 
 pub async fn handler(command: &NewRoundCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
 
-    let session = SessionItem::from_db(&command.session_id, &db).await?;
+    let session =
+        authorize::get_owned_session(&command.connection_id, &command.session_id, &db).await?;
 
     let game_id = if let Some(game_id) = session.game_id {
         game_id
@@ -28,20 +29,17 @@ pub async fn handler(command: &NewRoundCommand) -> Result<String, LogicError> {
     game.version += 1;
     // game.round_id += 1;
     game.modified_action = GameAction::NewRound;
-    game.modified_by = command.session_id.clone();
+    game.modified_by = command.session_id.to_string();
     game.modified_at = Utc::now();
     for player in game.players.iter_mut() {
         player.finished = false;
         // player.outcome = ;
         // player.rolls = Vec::new();
     }
-    db.write_single(game.save()?).await?;
-
-    println!("Sending game state notification");
-    let command = SendGameStateNotificationCommand {
+    let event = DomainEvent::NewRoundStarted {
         game_id: game_id.clone(),
     };
-    send_game_state_notification::handler(&command).await?;
+    db.write(vec![game.save()?, outbox::event(event)?]).await?;
 
     Ok(game_id)
 }
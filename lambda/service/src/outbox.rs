@@ -0,0 +1,50 @@
+use aws_sdk_dynamodb::types::TransactWriteItem;
+use domain::errors::LogicError;
+use domain::events::DomainEvent;
+use notifier::Message;
+use scheduler::ScheduledJob;
+use storage::game_table::GameItem;
+use storage::leaderboard_table::LeaderboardItem;
+use storage::outbox_table::{OutboxEntry, OutboxItem};
+
+// Handlers add these to the transaction holding their state change, and
+// relay_outbox delivers them once it commits
+
+pub fn event(event: DomainEvent) -> Result<TransactWriteItem, LogicError> {
+    OutboxItem::new(OutboxEntry::Event { event }).save()
+}
+
+pub fn notification(
+    connection_id: &str,
+    message: &Message,
+) -> Result<TransactWriteItem, LogicError> {
+    let entry = OutboxEntry::Notification {
+        connection_id: connection_id.to_string(),
+        data: message.render()?,
+    };
+    OutboxItem::new(entry).save()
+}
+
+pub fn round_finished(game: &GameItem) -> Result<TransactWriteItem, LogicError> {
+    event(DomainEvent::RoundFinished {
+        game_id: game.game_id.clone(),
+        round_id: game.round_count,
+        mr_eleven: game.mr_eleven.clone(),
+    })
+}
+
+pub fn schedule(job: &ScheduledJob) -> Result<TransactWriteItem, LogicError> {
+    let entry = OutboxEntry::Schedule {
+        command: job.command.clone(),
+        run_at: job.run_at,
+    };
+    OutboxItem::new(entry).save()
+}
+
+pub fn leaderboard(entries: Vec<LeaderboardItem>) -> Result<Option<TransactWriteItem>, LogicError> {
+    if entries.is_empty() {
+        return Ok(None);
+    }
+    let item = OutboxItem::new(OutboxEntry::Leaderboard { entries }).save()?;
+    Ok(Some(item))
+}
@@ -0,0 +1,24 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use storage::session_table::SessionItem;
+
+/// Creates a random token to hand to the client. Only its hash is stored,
+/// so a leaked session item can't be used to take over the session.
+pub fn generate() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+pub fn is_valid(session: &SessionItem, token: &str) -> bool {
+    match session.reconnect_token_hash {
+        Some(ref expected) => expected == &hash(token),
+        None => false,
+    }
+}
@@ -0,0 +1,15 @@
+use metrics::names::{ROLL_OUTCOMES, ROUNDS_FINISHED};
+use metrics::IMetrics;
+use storage::game_table::{GameItem, RollResultNote};
+
+pub async fn round_finished(game: &GameItem) {
+    let metrics = metrics::get().await;
+    metrics.increment(ROUNDS_FINISHED, &[]);
+    for player in &game.players {
+        let note = match player.outcome {
+            RollResultNote::None => "NONE",
+            outcome => outcome.as_str(),
+        };
+        metrics.increment(ROLL_OUTCOMES, &[("Note", note)]);
+    }
+}
@@ -0,0 +1,67 @@
+use domain::errors::LogicError;
+use event_publisher::IEventPublisher;
+use notifier::INotifier;
+use scheduler::{IScheduler, ScheduledJob};
+use serde_json::json;
+use storage::outbox_table::{OutboxEntry, OutboxItem};
+use storage::IDynamoDbClient;
+use tokio::sync::Mutex;
+
+use crate::update_leaderboards;
+
+// Entries are deleted after they're delivered, so two drains at once would
+// deliver some twice
+static DRAINING: Mutex<()> = Mutex::const_new(());
+
+/// Delivers every pending entry. In AWS the outbox table's stream calls
+/// `relay` instead, this is for in memory servers and tests.
+pub async fn handler() -> Result<String, LogicError> {
+    let _draining = DRAINING.lock().await;
+    let db = storage::get().await;
+
+    let mut delivered = 0;
+    let mut failed = 0;
+    for item in OutboxItem::pending(&db).await? {
+        match relay(&item).await {
+            Ok(()) => delivered += 1,
+            Err(e) => {
+                println!("Failed to relay {}: {e}", item.id);
+                failed += 1;
+            }
+        }
+    }
+    Ok(json!({"delivered": delivered, "failed": failed}).to_string())
+}
+
+/// Delivers the entry then deletes it, so one that fails is tried again.
+pub async fn relay(item: &OutboxItem) -> Result<(), LogicError> {
+    let db = storage::get().await;
+    deliver(&item.entry).await?;
+    db.write_single(item.delete()?).await
+}
+
+async fn deliver(entry: &OutboxEntry) -> Result<(), LogicError> {
+    let result = match entry {
+        OutboxEntry::Event { event } => event_publisher::get().await.publish(event).await,
+        OutboxEntry::Notification {
+            connection_id,
+            data,
+        } => notifier::get().await.post(connection_id, data).await,
+        OutboxEntry::Leaderboard { entries } => update_leaderboards::apply(entries).await,
+        OutboxEntry::Schedule { command, run_at } => {
+            let job = ScheduledJob {
+                command: command.clone(),
+                run_at: *run_at,
+            };
+            scheduler::get().await.schedule(&job).await
+        }
+    };
+    match result {
+        // Retrying won't bring back a closed connection
+        Err(LogicError::ConnectionGone(e)) => {
+            println!("Dropping {entry:?}, connection gone: {e}");
+            Ok(())
+        }
+        result => result,
+    }
+}
@@ -1,8 +1,19 @@
-use crate::{finish_round, send_game_state_notification};
+#![allow(
+    clippy::len_zero,
+    clippy::needless_return,
+    clippy::redundant_field_names
+)]
+
+use crate::{
+    authorize, finish_round, outbox, record_metrics, update_leaderboards, update_profiles,
+};
 use chrono::Utc;
-use domain::commands::{RollDiceCommand, SendGameStateNotificationCommand};
+use domain::commands::RollDiceCommand;
 use domain::default_hash_map::DefaultHashMap;
 use domain::errors::LogicError;
+use domain::events::DomainEvent;
+use metrics::names::ROLLS;
+use metrics::IMetrics;
 use rand::Rng;
 use std::collections::HashMap;
 use std::vec;
@@ -10,13 +21,14 @@ use storage::game_table::{
     DiceItem, DiceType, GameAction, GameItem, RollItem, RollResultItem, RollResultNote,
     RollResultType,
 };
-use storage::session_table::SessionItem;
 use storage::IDynamoDbClient;
 
 pub async fn handler(command: &RollDiceCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
+    let metrics = metrics::get().await;
 
-    let session = SessionItem::from_db(&command.session_id, &db).await?;
+    let session =
+        authorize::get_owned_session(&command.connection_id, &command.session_id, &db).await?;
     let game_id = if let Some(game_id) = session.game_id {
         game_id
     } else {
@@ -28,7 +40,7 @@ pub async fn handler(command: &RollDiceCommand) -> Result<String, LogicError> {
     let player = game
         .players
         .iter_mut()
-        .find(|p| p.player_id == command.session_id)
+        .find(|p| p.player_id == command.session_id.as_str())
         .ok_or(LogicError::InvalidGameState(
             "Player not in game".to_string(),
         ))?;
@@ -43,28 +55,52 @@ pub async fn handler(command: &RollDiceCommand) -> Result<String, LogicError> {
     };
 
     let roll = roll_dice(&player.rolls, player.win_counter, &player.nickname);
+    let dice = roll.dice.iter().map(|d| d.value).collect();
     player.rolls.push(roll);
 
     let result = calculate_individual_result(&player.rolls, is_mr_eleven);
     player.finished = result.turn_finished;
     player.outcome = result.note;
+    let event = DomainEvent::DiceRolled {
+        game_id: game_id.clone(),
+        session_id: command.session_id.to_string(),
+        dice,
+        turn_finished: result.turn_finished,
+    };
     game.version += 1;
     game.modified_action = GameAction::RollDice;
-    game.modified_by = command.session_id.clone();
+    game.modified_by = command.session_id.to_string();
     game.modified_at = Utc::now();
 
+    let previous_mr_eleven = game.mr_eleven.clone();
     let is_round_finished = game.players.iter().all(|p| p.finished);
+    let mut round = None;
     if is_round_finished {
         finish_round::handler(&mut game)?;
+        if game.round_finished {
+            round = Some(game.record_round());
+        }
     }
 
-    db.write_single(game.save()?).await?;
-
-    println!("Sending game state notification");
-    let command = SendGameStateNotificationCommand {
-        game_id: game_id.clone(),
-    };
-    send_game_state_notification::handler(&command).await?;
+    let mut transactions = vec![outbox::event(event)?];
+    if let Some(round) = round {
+        transactions.push(round.save()?);
+        transactions.push(outbox::round_finished(&game)?);
+        let profiles = update_profiles::round_finished(&game, &previous_mr_eleven, &db).await?;
+        // Boards are shared between games, so the relay updates them
+        // outside this transaction
+        let leaderboards = update_leaderboards::round_finished(&mut game, &profiles);
+        transactions.extend(outbox::leaderboard(leaderboards)?);
+        for profile in profiles {
+            transactions.push(profile.save()?);
+        }
+    }
+    transactions.push(game.save()?);
+    db.write(transactions).await?;
+    metrics.increment(ROLLS, &[]);
+    if is_round_finished && game.round_finished {
+        record_metrics::round_finished(&game).await;
+    }
 
     Ok("Ok".to_string())
 }
@@ -0,0 +1,11 @@
+use crate::check_session_timeout;
+use domain::commands::ScheduledCommand;
+use domain::errors::LogicError;
+
+pub async fn handler(command: &ScheduledCommand) -> Result<String, LogicError> {
+    match command {
+        ScheduledCommand::CheckSessionTimeout(command) => {
+            check_session_timeout::handler(command).await
+        }
+    }
+}
@@ -1,47 +1,139 @@
-use domain::commands::SendGameStateNotificationCommand;
+use crate::destroy_connection;
+use chrono::{DateTime, Utc};
+use domain::commands::DestroyConnectionCommand;
 use domain::errors::LogicError;
-use notifier::{self, ActionType, GameStateMessage, INotifier, Message, RoundStateMessage};
+use domain::values::ConnectionId;
+use futures::stream::{self, StreamExt};
+use notifier::{
+    self, ActionType, GameStateMessage, INotifier, Message, PlayerStateMessage, RoundStateMessage,
+};
+use serde::Serialize;
+use storage::game_table::{ConnectionStatus, PlayerItem};
 use storage::{game_table::GameItem, session_table::SessionItem};
 
-pub async fn handler(command: &SendGameStateNotificationCommand) -> Result<String, LogicError> {
+// Overlaps the round trips without hitting API Gateway's connection limits
+const MAX_CONCURRENT_SENDS: usize = 10;
+
+/// How sending the game to one player went.
+#[derive(Debug, PartialEq, Serialize)]
+pub enum Delivery {
+    Sent,
+    /// The client had already gone, so the connection was dropped as though
+    /// it had disconnected.
+    Gone,
+    Failed(String),
+}
+
+#[derive(Debug, Serialize)]
+pub struct Recipient {
+    pub session_id: String,
+    pub delivery: Delivery,
+}
+
+/// Sends the game as given, rather than as it is now. One player failing
+/// doesn't stop the others being sent it.
+pub async fn send(game: &GameItem) -> Result<Vec<Recipient>, LogicError> {
     let db = storage::get().await;
-    let notifier = notifier::get().await;
+    let now = Utc::now();
 
-    let game = GameItem::from_db(&command.game_id, &db).await?;
-
-    // let player_message = game
-    //     .players
-    //     .iter()
-    //     .map(|p| PlayerStateMessage {
-    //         id: p.player_id.clone(),
-    //         nickname: p.nickname.clone(),
-    //         turn_finished: p.turn_finished,
-    //         win_count: p.win_count,
-    //         roll_result: p.roll_result.clone(),
-    //         connection_status: p.connection_status.clone(),
-    //         roll_total: p.roll_total,
-    //         dice_value: p.dice_value.clone(),
-    //     })
-    //     .collect::<Vec<_>>();
+    let player_message = game
+        .players
+        .iter()
+        .map(|p| to_player_message(p, now))
+        .collect::<Vec<_>>();
     let game_message = GameStateMessage {
         game_id: game.game_id.clone(),
-        // players: player_message,
+        players: player_message,
         round: RoundStateMessage {
             complete: game.round_finished,
         },
     };
-    let message = Message::new(ActionType::GameState(game_message));
+    let data = Message::new(ActionType::GameState(game_message)).render()?;
 
+    // Dropped players have no connection to send to, they're sent the game
+    // when they reconnect
     let session_ids = game
         .players
         .iter()
+        .filter(|p| p.connection_status(now) == ConnectionStatus::Connected)
         .map(|p| p.player_id.clone())
         .collect::<Vec<_>>();
 
-    for session_id in session_ids {
-        let session = SessionItem::from_db(&session_id, &db).await?;
-        notifier.notify(&session.connection_id, &message).await?;
+    let sessions = SessionItem::from_db_batch(&session_ids, &db).await?;
+
+    let sent = stream::iter(session_ids.into_iter().zip(sessions))
+        .map(|(session_id, session)| {
+            let data = &data;
+            async move {
+                let delivery = match &session {
+                    Ok(session) => deliver(&session.connection_id, data).await,
+                    Err(e) => Delivery::Failed(e.to_string()),
+                };
+                let recipient = Recipient {
+                    session_id,
+                    delivery,
+                };
+                (recipient, session)
+            }
+        })
+        .buffered(MAX_CONCURRENT_SENDS)
+        .collect::<Vec<_>>()
+        .await;
+
+    // Each drop saves the same game, so they'd only contend if run together
+    let mut recipients = vec![];
+    for (recipient, session) in sent {
+        if let (Delivery::Gone, Ok(session)) = (&recipient.delivery, session) {
+            mark_stale(&session.connection_id).await;
+        }
+        recipients.push(recipient);
     }
+    Ok(recipients)
+}
+
+async fn deliver(connection_id: &str, data: &str) -> Delivery {
+    let notifier = notifier::get().await;
+    match notifier.post(connection_id, data).await {
+        Ok(()) => Delivery::Sent,
+        Err(LogicError::ConnectionGone(_)) => Delivery::Gone,
+        Err(e) => Delivery::Failed(e.to_string()),
+    }
+}
 
-    Ok(command.game_id.clone())
+// API Gateway can drop a client without a $disconnect reaching us, so start
+// the reconnect grace period here instead
+async fn mark_stale(connection_id: &str) {
+    let Ok(connection_id) = ConnectionId::parse(connection_id) else {
+        return;
+    };
+    let command = DestroyConnectionCommand { connection_id };
+    match destroy_connection::handler(&command).await {
+        // Already handled by $disconnect
+        Ok(_) | Err(LogicError::ItemNotFound(_)) => {}
+        Err(e) => println!("Failed to drop stale connection: {e}"),
+    }
+}
+
+/// Describes the player's latest roll.
+fn to_player_message(player: &PlayerItem, now: DateTime<Utc>) -> PlayerStateMessage {
+    let dice: Vec<i32> = player
+        .rolls
+        .last()
+        .map(|roll| roll.dice.iter().map(|dice| dice.value).collect())
+        .unwrap_or_default();
+    PlayerStateMessage {
+        id: player.player_id.clone(),
+        nickname: player.nickname.clone(),
+        turn_finished: player.finished,
+        win_count: player.win_counter,
+        roll_result: player.outcome.as_str().to_string(),
+        connection_status: player.connection_status(now).as_str().to_string(),
+        reconnect_deadline: player.reconnect_deadline.map(|d| d.to_rfc3339()),
+        roll_total: dice.iter().sum(),
+        dice_value: dice
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+    }
 }
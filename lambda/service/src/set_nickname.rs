@@ -1,44 +1,55 @@
+use crate::{authorize, outbox};
 use chrono::Utc;
 use domain::commands::SetNicknameCommand;
-use domain::errors::LogicError;
+use domain::errors::{LogicError, ValidationError};
+use domain::events::DomainEvent;
+use domain::values::ConnectionId;
 use notifier::{self, ActionType, INotifier, Message, SetNicknameMessage};
-use std::collections::HashSet;
-use storage::session_table::{SessionAction, SessionItem};
+use storage::session_table::SessionAction;
 use storage::IDynamoDbClient;
 
 pub async fn handler(command: &SetNicknameCommand) -> Result<String, LogicError> {
     let db = storage::get().await;
-    let notifier = notifier::get().await;
-
-    let mut session = SessionItem::from_db(&command.session_id, &db).await?;
 
-    let is_valid = is_valid_nickname(&command.nickname);
-    if is_valid {
-        session.account_id = command.account_id.clone();
-        session.modified_action = SessionAction::SetNickname;
-        session.modified_at = Utc::now();
-        session.nickname = Some(command.nickname.clone());
-        session.version += 1;
+    let mut session =
+        authorize::get_owned_session(&command.connection_id, &command.session_id, &db).await?;
 
-        db.write_single(session.save()?).await?;
-        let message = create_success_message(&command.session_id, &command.nickname);
-        notifier.notify(&session.connection_id, &message).await?;
-    } else {
-        let message = create_failure_message();
-        notifier.notify(&session.connection_id, &message).await?;
+    // Account IDs are only set by authenticating, the client can't claim one
+    if let Some(ref account_id) = command.account_id {
+        if session.account_id.as_deref() != Some(account_id.as_str()) {
+            println!("Account ID does not match authenticated account");
+            return Err(LogicError::NotAllowed);
+        }
     }
-    Ok(command.session_id.clone())
-}
+    session.modified_action = SessionAction::SetNickname;
+    session.modified_at = Utc::now();
+    session.nickname = Some(command.nickname.to_string());
+    session.version += 1;
 
-fn is_valid_nickname(nickname: &str) -> bool {
-    let invalid_names: HashSet<&str> = ["MR ELEVEN", "MRELEVEN", "MR 11", "MR11"]
-        .iter()
-        .cloned()
-        .collect();
-    let length = nickname.len();
-    let name_upper = nickname.trim().to_uppercase();
+    let message = create_success_message(&command.session_id, &command.nickname);
+    let event = DomainEvent::NicknameChanged {
+        session_id: command.session_id.to_string(),
+        nickname: command.nickname.to_string(),
+    };
+    db.write(vec![
+        session.save()?,
+        outbox::notification(&session.connection_id, &message)?,
+        outbox::event(event)?,
+    ])
+    .await?;
+    Ok(command.session_id.to_string())
+}
 
-    length >= 2 && length <= 69 && !invalid_names.contains(name_upper.as_str())
+/// Nicknames that fail validation are reported back to the player rather
+/// than surfaced as an error, so the client can prompt for another one.
+pub async fn reject(
+    connection_id: &ConnectionId,
+    error: &ValidationError,
+) -> Result<String, LogicError> {
+    let notifier = notifier::get().await;
+    let message = create_failure_message(&error.reason);
+    notifier.notify(connection_id, &message).await?;
+    Ok("Invalid nickname".to_string())
 }
 
 fn create_success_message(session_id: &str, nickname: &str) -> Message {
@@ -49,8 +60,6 @@ fn create_success_message(session_id: &str, nickname: &str) -> Message {
     Message::new(ActionType::SetNickname(nickname_message))
 }
 
-fn create_failure_message() -> Message {
-    Message::new_err(ActionType::SetNicknameFailure(
-        "Invalid nickname".to_string(),
-    ))
+fn create_failure_message(reason: &str) -> Message {
+    Message::new_err(ActionType::SetNicknameFailure(reason.to_string()))
 }
@@ -1,7 +1,8 @@
+use crate::{connection_status, reconnect_token};
 use chrono::Utc;
 use domain::commands::SetSessionCommand;
 use domain::errors::LogicError;
-use notifier::{self, ActionType, INotifier, Message};
+use notifier::{self, ActionType, GetSessionMessage, INotifier, Message};
 use storage::session_table::{SessionAction, SessionItem};
 use storage::websocket_table::WebsocketItem;
 use storage::IDynamoDbClient;
@@ -13,23 +14,62 @@ pub async fn handler(command: &SetSessionCommand) -> Result<String, LogicError>
     // TODO: if session doesnt exist, create a new one instead
 
     let mut connection = WebsocketItem::from_db(&command.connection_id, &db).await?;
-    connection.session_id = Some(command.session_id.clone());
+    let mut session = SessionItem::from_db(&command.session_id, &db).await?;
+    if !reconnect_token::is_valid(&session, &command.reconnect_token) {
+        println!("Invalid reconnect token");
+        return Err(LogicError::NotAllowed);
+    }
+
+    let mut items = vec![];
+    if session.connection_id != command.connection_id.as_str() {
+        // Unbind the previous connection so it can no longer act as this session
+        match WebsocketItem::from_db(&session.connection_id, &db).await {
+            Ok(mut old_connection) => {
+                old_connection.session_id = None;
+                old_connection.version += 1;
+                old_connection.modified_at = Utc::now();
+                items.push(old_connection.save()?);
+            }
+            Err(LogicError::ItemNotFound(_)) => println!("Previous connection already deleted"),
+            Err(e) => return Err(e),
+        }
+    }
+
+    connection.session_id = Some(command.session_id.to_string());
     connection.version += 1;
     connection.modified_at = Utc::now();
 
-    let mut session = SessionItem::from_db(&command.session_id, &db).await?;
-    session.connection_id = command.connection_id.clone();
+    let token = reconnect_token::generate();
+    session.connection_id = command.connection_id.to_string();
+    session.reconnect_token_hash = Some(reconnect_token::hash(&token));
     session.version += 1;
     session.modified_at = Utc::now();
     session.modified_action = SessionAction::Reconnected;
 
     println!("Saving to database");
-    db.write(vec![session.save()?, connection.save()?]).await?;
+    items.push(session.save()?);
+    items.push(connection.save()?);
+    db.write(items).await?;
 
     println!("Notifying connections");
-    let message = Message::new(ActionType::GetSession(command.session_id.clone()));
-    notifier.notify(&connection.connection_id, &message).await?;
+    let message = Message::new(ActionType::GetSession(GetSessionMessage {
+        session_id: command.session_id.to_string(),
+        reconnect_token: Some(token),
+    }));
+    // The session has moved over whether or not the client hears about it,
+    // so the player is still marked connected
+    if let Err(e) = notifier.notify(&connection.connection_id, &message).await {
+        println!(
+            "Failed to send session to {}: {e}",
+            connection.connection_id
+        );
+    }
+
+    if let Some(game_id) = &session.game_id {
+        println!("Marking player as connected");
+        connection_status::update(game_id, &session.session_id, None).await?;
+    }
 
     println!("Returning");
-    Ok(command.session_id.clone())
+    Ok(command.session_id.to_string())
 }
@@ -0,0 +1,147 @@
+use crate::{destroy_connection, destroy_session, leave_game};
+use chrono::{DateTime, Utc};
+use domain::commands::{DestroyConnectionCommand, DestroySessionCommand, LeaveGameCommand};
+use domain::errors::LogicError;
+use domain::values::ConnectionId;
+use serde::Serialize;
+use storage::game_table::{GameItem, RoundItem};
+use storage::session_table::SessionItem;
+use storage::websocket_table::WebsocketItem;
+use storage::{DynamoDbClient, IDynamoDbClient};
+
+#[derive(Default, Serialize)]
+struct SweepView {
+    connections: usize,
+    sessions: usize,
+    games: usize,
+    rounds: usize,
+    failures: usize,
+}
+
+/// Tears down connections, sessions and games that have seen no activity
+/// within their TTL, through the same handlers as a clean exit so remaining
+/// players are told, and deletes rounds past theirs. One failure doesn't stop
+/// the sweep, it's retried on the next run.
+pub async fn handler() -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let now = Utc::now();
+    let mut view = SweepView::default();
+
+    for connection in WebsocketItem::expired(now, &db).await? {
+        println!("Sweeping connection {}", connection.connection_id);
+        match sweep_connection(&connection).await {
+            Ok(()) => view.connections += 1,
+            Err(e) => failed(&mut view, &connection.connection_id, e),
+        }
+    }
+    for session in SessionItem::expired(now, &db).await? {
+        match sweep_session(&session, now, &db).await {
+            Ok(true) => view.sessions += 1,
+            Ok(false) => (),
+            Err(e) => failed(&mut view, &session.session_id, e),
+        }
+    }
+    for game in GameItem::expired(now, &db).await? {
+        println!("Sweeping game {}", game.game_id);
+        match sweep_game(&game, now, &db).await {
+            Ok(()) => view.games += 1,
+            Err(e) => failed(&mut view, &game.game_id, e),
+        }
+    }
+    for round in RoundItem::expired(now, &db).await? {
+        match db.write_single(round.delete()?).await {
+            Ok(()) => view.rounds += 1,
+            Err(e) => failed(&mut view, &round.instance_id, e),
+        }
+    }
+
+    serde_json::to_string(&view).map_err(|e| LogicError::SerializationError(e.to_string()))
+}
+
+fn failed(view: &mut SweepView, id: &str, error: LogicError) {
+    println!("Failed to sweep {id}: {error}");
+    view.failures += 1;
+}
+
+async fn sweep_connection(connection: &WebsocketItem) -> Result<(), LogicError> {
+    let command = DestroyConnectionCommand {
+        connection_id: ConnectionId::parse(&connection.connection_id)?,
+    };
+    match destroy_connection::handler(&command).await {
+        Err(LogicError::ItemNotFound(_)) => Ok(()),
+        result => result.map(|_| ()),
+    }
+}
+
+/// A session that's still connected is left alone, it expires once the
+/// connection does.
+async fn sweep_session(
+    session: &SessionItem,
+    now: DateTime<Utc>,
+    db: &DynamoDbClient,
+) -> Result<bool, LogicError> {
+    if is_connected(session, now, db).await? {
+        return Ok(false);
+    }
+    println!("Sweeping session {}", session.session_id);
+    let command = DestroySessionCommand {
+        connection_id: None,
+        session_id: session.session_id.clone(),
+    };
+    match destroy_session::handler(&command).await {
+        Err(LogicError::ItemNotFound(_)) => Ok(true),
+        result => result.map(|_| true),
+    }
+}
+
+/// Every player leaves, which deletes the game once the last one has gone.
+async fn sweep_game(
+    game: &GameItem,
+    now: DateTime<Utc>,
+    db: &DynamoDbClient,
+) -> Result<(), LogicError> {
+    if game.players.is_empty() {
+        return db.write_single(game.delete()?).await;
+    }
+    for player in &game.players {
+        // A player who has moved on, or is still connected, keeps their
+        // session and only the game needs updating
+        let abandoned = match SessionItem::from_db(&player.player_id, db).await {
+            Ok(session) if session.game_id.as_ref() == Some(&game.game_id) => {
+                !is_connected(&session, now, db).await?
+            }
+            Ok(_) | Err(LogicError::ItemNotFound(_)) => false,
+            Err(e) => return Err(e),
+        };
+        let result = if abandoned {
+            let command = DestroySessionCommand {
+                connection_id: None,
+                session_id: player.player_id.clone(),
+            };
+            destroy_session::handler(&command).await
+        } else {
+            let command = LeaveGameCommand {
+                game_id: game.game_id.clone(),
+                session_id: player.player_id.clone(),
+            };
+            leave_game::handler(&command).await
+        };
+        match result {
+            Err(LogicError::ItemNotFound(_)) => return Ok(()),
+            result => result.map(|_| ())?,
+        }
+    }
+    Ok(())
+}
+
+async fn is_connected(
+    session: &SessionItem,
+    now: DateTime<Utc>,
+    db: &DynamoDbClient,
+) -> Result<bool, LogicError> {
+    match WebsocketItem::from_db(&session.connection_id, db).await {
+        Ok(connection) => Ok(!connection.is_expired(now)),
+        Err(LogicError::ItemNotFound(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,83 @@
+use domain::errors::LogicError;
+use domain::values::LeaderboardStat;
+use storage::game_table::{GameItem, RollResultNote, RollResultType};
+use storage::leaderboard_table::{LeaderboardItem, LeaderboardScope};
+use storage::profile_table::ProfileItem;
+use storage::IDynamoDbClient;
+
+// Global boards mirror lifetime profile totals, group boards the totals for
+// this game. Both are absolute, so the relay can save them more than once.
+pub fn round_finished(game: &mut GameItem, profiles: &[ProfileItem]) -> Vec<LeaderboardItem> {
+    let mut entries = vec![];
+    let global = LeaderboardScope::Global;
+    for profile in profiles {
+        let nickname = game
+            .players
+            .iter()
+            .find(|p| p.account_id.as_deref() == Some(profile.account_id.as_str()))
+            .map(|p| p.nickname.as_str())
+            .unwrap_or_default();
+        let pools = profile
+            .outcome_counts
+            .get(RollResultNote::Pool.as_str())
+            .copied()
+            .unwrap_or(0);
+        let scores = [
+            (LeaderboardStat::Wins, profile.rounds_won),
+            (LeaderboardStat::WinStreak, profile.longest_win_streak),
+            (LeaderboardStat::Pools, pools),
+        ];
+        for (stat, score) in scores {
+            if score > 0 {
+                let entry =
+                    LeaderboardItem::new(&global, stat, &profile.account_id, nickname, score);
+                entries.push(entry);
+            }
+        }
+    }
+
+    let group = LeaderboardScope::Group(game.instance_id.clone());
+    for player in &mut game.players {
+        let is_winner = player.outcome_type == RollResultType::Winner;
+        let is_pool = player.outcome == RollResultNote::Pool;
+        if is_winner {
+            player.rounds_won += 1;
+        }
+        if is_pool {
+            player.pools += 1;
+        }
+        let account_id = if let Some(ref account_id) = player.account_id {
+            account_id
+        } else {
+            continue;
+        };
+        let mut scores = vec![];
+        if is_winner {
+            scores.push((LeaderboardStat::Wins, player.rounds_won));
+        }
+        if player.win_counter > 0 {
+            scores.push((LeaderboardStat::WinStreak, player.win_counter));
+        }
+        if is_pool {
+            scores.push((LeaderboardStat::Pools, player.pools));
+        }
+        for (stat, score) in scores {
+            let entry = LeaderboardItem::new(&group, stat, account_id, &player.nickname, score);
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// Saves entries relayed from the outbox. A failed condition means the entry
+/// already holds this score or a later round's higher one.
+pub async fn apply(entries: &[LeaderboardItem]) -> Result<(), LogicError> {
+    let db = storage::get().await;
+    for entry in entries {
+        match db.write_single(entry.save()?).await {
+            Err(LogicError::ConditionalCheckFailed(_)) => {}
+            result => result?,
+        }
+    }
+    Ok(())
+}
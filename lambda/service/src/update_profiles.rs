@@ -0,0 +1,75 @@
+use chrono::Utc;
+use domain::errors::LogicError;
+use std::collections::HashMap;
+use storage::game_table::{GameItem, PlayerItem, RollResultNote, RollResultType};
+use storage::profile_table::ProfileItem;
+use storage::DynamoDbClient;
+
+pub async fn game_created(
+    account_id: &str,
+    db: &DynamoDbClient,
+) -> Result<ProfileItem, LogicError> {
+    let mut profile = load_for_update(account_id, db).await?;
+    profile.games_played += 1;
+    Ok(profile)
+}
+
+// Accumulates the settled round into the profile of every player with an account
+pub async fn round_finished(
+    game: &GameItem,
+    previous_mr_eleven: &Option<String>,
+    db: &DynamoDbClient,
+) -> Result<Vec<ProfileItem>, LogicError> {
+    let mut profiles: HashMap<String, ProfileItem> = HashMap::new();
+    for player in &game.players {
+        let account_id = if let Some(ref account_id) = player.account_id {
+            account_id
+        } else {
+            continue;
+        };
+        if !profiles.contains_key(account_id) {
+            let profile = load_for_update(account_id, db).await?;
+            profiles.insert(account_id.clone(), profile);
+        }
+        let profile = profiles.get_mut(account_id).unwrap();
+        add_round(profile, player, &game.mr_eleven, previous_mr_eleven);
+    }
+    Ok(profiles.into_values().collect())
+}
+
+async fn load_for_update(account_id: &str, db: &DynamoDbClient) -> Result<ProfileItem, LogicError> {
+    match ProfileItem::from_db(account_id, db).await {
+        Ok(mut profile) => {
+            profile.modified_at = Utc::now();
+            profile.version += 1;
+            Ok(profile)
+        }
+        Err(LogicError::ItemNotFound(_)) => Ok(ProfileItem::new(account_id)),
+        Err(e) => Err(e),
+    }
+}
+
+fn add_round(
+    profile: &mut ProfileItem,
+    player: &PlayerItem,
+    mr_eleven: &Option<String>,
+    previous_mr_eleven: &Option<String>,
+) {
+    profile.rounds_played += 1;
+    if player.outcome_type == RollResultType::Winner {
+        profile.rounds_won += 1;
+    }
+    profile.longest_win_streak = profile.longest_win_streak.max(player.win_counter);
+    let is_mr_eleven = mr_eleven.as_deref() == Some(player.player_id.as_str());
+    let was_mr_eleven = previous_mr_eleven.as_deref() == Some(player.player_id.as_str());
+    if is_mr_eleven && !was_mr_eleven {
+        profile.times_mr_eleven += 1;
+    }
+    if player.outcome != RollResultNote::None {
+        let count = profile
+            .outcome_counts
+            .entry(player.outcome.as_str().to_string())
+            .or_insert(0);
+        *count += 1;
+    }
+}
@@ -0,0 +1,27 @@
+use crate::get_profile;
+use domain::commands::GetAccountCommand;
+use domain::errors::LogicError;
+use notifier::ProfileMessage;
+use serde::Serialize;
+use storage::profile_table::ProfileItem;
+
+#[derive(Serialize)]
+struct AccountView {
+    #[serde(rename = "accountId")]
+    account_id: String,
+    email: String,
+    username: String,
+    profile: ProfileMessage,
+}
+
+pub async fn handler(command: &GetAccountCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let profile = ProfileItem::from_db_or_new(&command.account_id, &db).await?;
+    let view = AccountView {
+        account_id: command.account_id.to_string(),
+        email: command.email.clone(),
+        username: command.username.clone(),
+        profile: get_profile::to_message(&profile),
+    };
+    serde_json::to_string(&view).map_err(|e| LogicError::SerializationError(e.to_string()))
+}
@@ -0,0 +1,114 @@
+use crate::authorize;
+use chrono::{DateTime, Utc};
+use domain::commands::{GetGameCommand, GetGameRoundsCommand};
+use domain::errors::LogicError;
+use serde::Serialize;
+use storage::game_table::{PlayerItem, RoundItem};
+
+#[derive(Serialize)]
+struct PlayerView {
+    #[serde(rename = "playerId")]
+    player_id: String,
+    nickname: String,
+    #[serde(rename = "winCounter")]
+    win_counter: i32,
+    finished: bool,
+    outcome: String,
+    rolls: Vec<Vec<i32>>,
+}
+
+#[derive(Serialize)]
+struct GameView {
+    #[serde(rename = "gameId")]
+    game_id: String,
+    #[serde(rename = "mrEleven")]
+    mr_eleven: Option<String>,
+    #[serde(rename = "roundFinished")]
+    round_finished: bool,
+    players: Vec<PlayerView>,
+}
+
+#[derive(Serialize)]
+struct RoundView {
+    #[serde(rename = "roundId")]
+    round_id: i32,
+    #[serde(rename = "finishedAt")]
+    finished_at: DateTime<Utc>,
+    #[serde(rename = "mrEleven")]
+    mr_eleven: Option<String>,
+    players: Vec<PlayerView>,
+}
+
+#[derive(Serialize)]
+struct RoundsView {
+    #[serde(rename = "gameId")]
+    game_id: String,
+    rounds: Vec<RoundView>,
+    #[serde(rename = "nextOffset")]
+    next_offset: Option<usize>,
+}
+
+pub async fn handler(command: &GetGameCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let game = authorize::get_account_game(&command.account_id, &command.game_id, &db).await?;
+    let view = GameView {
+        game_id: game.game_id.clone(),
+        mr_eleven: game.mr_eleven.clone(),
+        round_finished: game.round_finished,
+        players: game.players.iter().map(to_player_view).collect(),
+    };
+    to_json(&view)
+}
+
+/// Rounds are returned newest first.
+pub async fn rounds(command: &GetGameRoundsCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let game = authorize::get_account_game(&command.account_id, &command.game_id, &db).await?;
+    let round_count = usize::try_from(game.round_count).unwrap_or(0);
+    let round_ids: Vec<i32> = (1..=game.round_count)
+        .rev()
+        .skip(command.page.offset)
+        .take(command.page.limit)
+        .collect();
+    let next_offset = command.page.offset + round_ids.len();
+    let next_offset = if next_offset < round_count {
+        Some(next_offset)
+    } else {
+        None
+    };
+    let rounds = RoundItem::from_db_batch(&game.instance_id, &round_ids, &db).await?;
+    let view = RoundsView {
+        game_id: game.game_id.clone(),
+        rounds: rounds.iter().map(to_round_view).collect(),
+        next_offset,
+    };
+    to_json(&view)
+}
+
+fn to_player_view(player: &PlayerItem) -> PlayerView {
+    PlayerView {
+        player_id: player.player_id.clone(),
+        nickname: player.nickname.clone(),
+        win_counter: player.win_counter,
+        finished: player.finished,
+        outcome: player.outcome.as_str().to_string(),
+        rolls: player
+            .rolls
+            .iter()
+            .map(|roll| roll.dice.iter().map(|dice| dice.value).collect())
+            .collect(),
+    }
+}
+
+fn to_round_view(round: &RoundItem) -> RoundView {
+    RoundView {
+        round_id: round.round_id,
+        finished_at: round.finished_at,
+        mr_eleven: round.mr_eleven.clone(),
+        players: round.players.iter().map(to_player_view).collect(),
+    }
+}
+
+fn to_json<T: Serialize>(view: &T) -> Result<String, LogicError> {
+    serde_json::to_string(view).map_err(|e| LogicError::SerializationError(e.to_string()))
+}
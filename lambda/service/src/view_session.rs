@@ -0,0 +1,32 @@
+use crate::authorize;
+use chrono::{DateTime, Utc};
+use domain::commands::GetSessionDetailsCommand;
+use domain::errors::LogicError;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct SessionView {
+    #[serde(rename = "sessionId")]
+    session_id: String,
+    #[serde(rename = "accountId")]
+    account_id: Option<String>,
+    nickname: Option<String>,
+    #[serde(rename = "gameId")]
+    game_id: Option<String>,
+    #[serde(rename = "modifiedAt")]
+    modified_at: DateTime<Utc>,
+}
+
+pub async fn handler(command: &GetSessionDetailsCommand) -> Result<String, LogicError> {
+    let db = storage::get().await;
+    let session =
+        authorize::get_account_session(&command.account_id, &command.session_id, &db).await?;
+    let view = SessionView {
+        session_id: session.session_id,
+        account_id: session.account_id,
+        nickname: session.nickname,
+        game_id: session.game_id,
+        modified_at: session.modified_at,
+    };
+    serde_json::to_string(&view).map_err(|e| LogicError::SerializationError(e.to_string()))
+}
@@ -0,0 +1,73 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use chrono::{TimeDelta, Utc};
+    use domain::errors::LogicError;
+    use service::allocate_game_code::allocate;
+    use storage::game_table::{GameItem, GAME_CODE_ALPHABET};
+    use storage::IDynamoDbClient;
+
+    #[tokio::test]
+    async fn allocates_unambiguous_codes() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_code = allocate(&db).await?;
+
+        assert_eq!(game_code.len(), config::get().game_code_length);
+        assert!(game_code.bytes().all(|c| GAME_CODE_ALPHABET.contains(&c)));
+        assert!(!game_code.contains(['0', 'O', '1', 'I']));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn only_reuses_codes_of_swept_games() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_id = GameItem::create_game_code();
+        db.write_single(GameItem::new(&game_id, "host").save()?)
+            .await?;
+        let game = GameItem::from_db(&game_id, &db).await?;
+        assert!(game.expires_at.unwrap() > Utc::now());
+        assert!(!game.is_expired(Utc::now()));
+
+        // A live game keeps its code
+        let result = db
+            .write_single(GameItem::new(&game_id, "other").save()?)
+            .await;
+        assert!(matches!(result, Err(LogicError::ConditionalCheckFailed(_))));
+
+        // Nobody saved it within the reservation
+        let mut game = GameItem::from_db(&game_id, &db).await?;
+        game.version += 1;
+        let mut abandoned = game.save()?;
+        let expired = (Utc::now() - TimeDelta::minutes(1)).timestamp();
+        abandoned.put.as_mut().unwrap().item.insert(
+            "expires_at".to_string(),
+            AttributeValue::N(expired.to_string()),
+        );
+        db.write_single(abandoned).await?;
+        assert!(GameItem::from_db(&game_id, &db)
+            .await?
+            .is_expired(Utc::now()));
+
+        // An abandoned game keeps its code until swept
+        let result = db
+            .write_single(GameItem::new(&game_id, "other").save()?)
+            .await;
+        assert!(matches!(result, Err(LogicError::ConditionalCheckFailed(_))));
+
+        let game = GameItem::from_db(&game_id, &db).await?;
+        db.write_single(game.delete()?).await?;
+        db.write_single(GameItem::new(&game_id, "other").save()?)
+            .await?;
+        let game = GameItem::from_db(&game_id, &db).await?;
+        assert_eq!(game.modified_by, "other");
+        assert_eq!(game.version, 0);
+        Ok(())
+    }
+}
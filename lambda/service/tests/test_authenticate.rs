@@ -0,0 +1,117 @@
+// The stub signing key only exists in in-memory builds
+#![cfg(feature = "in_memory")]
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use auth::stub;
+    use chrono::Utc;
+    use domain::commands::AuthenticateCommand;
+    use domain::errors::LogicError;
+    use domain::values::{ConnectionId, IdToken, SessionId};
+    use notifier::{self, INotifier};
+    use service::authenticate::handler;
+    use storage::session_table::{SessionAction, SessionItem};
+    use storage::websocket_table::WebsocketItem;
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    async fn create_session() -> Result<(String, String), LogicError> {
+        let db = storage::get().await;
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let session = SessionItem::new(&session_id, &connection_id);
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
+        Ok((connection_id, session_id))
+    }
+
+    #[tokio::test]
+    async fn stores_verified_account_id() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+        let start_time = Utc::now();
+        let (connection_id, session_id) = create_session().await?;
+
+        let account_id = Uuid::new_v4().to_string();
+        let request = AuthenticateCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            token: IdToken::parse(&stub::token_for(&account_id))?,
+        };
+        let result = handler(&request).await?;
+        assert_eq!(result, account_id);
+
+        // Notifies the connection
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_id);
+        assert_eq!(messages.len(), 1);
+
+        // Updates session item
+        let session = SessionItem::from_db(&session_id, &db).await?;
+        assert_eq!(session.account_id.unwrap(), account_id);
+        assert_eq!(session.modified_action, SessionAction::Authenticated);
+        assert!(session.modified_at > start_time);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_token_is_expired() -> Result<(), LogicError> {
+        test_setup::setup();
+        let (connection_id, session_id) = create_session().await?;
+
+        let mut claims = stub::claims_for(&Uuid::new_v4().to_string());
+        claims["exp"] = (Utc::now().timestamp() - 60).into();
+        let request = AuthenticateCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            token: IdToken::parse(&stub::sign(&claims))?,
+        };
+        let result = handler(&request).await;
+        assert!(matches!(result, Err(LogicError::AuthenticationError(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_token_is_tampered() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+        let (connection_id, session_id) = create_session().await?;
+
+        // Swap in a payload claiming a different subject, keeping the signature
+        let token = stub::token_for("victim");
+        let forged = stub::token_for("attacker");
+        let mut parts: Vec<&str> = token.split('.').collect();
+        parts[1] = forged.split('.').nth(1).unwrap();
+        let request = AuthenticateCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            token: IdToken::parse(&parts.join("."))?,
+        };
+        let result = handler(&request).await;
+        assert!(matches!(result, Err(LogicError::AuthenticationError(_))));
+
+        // Does not update session item
+        let session = SessionItem::from_db(&session_id, &db).await?;
+        assert!(session.account_id.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_audience_is_wrong() -> Result<(), LogicError> {
+        test_setup::setup();
+        let (connection_id, session_id) = create_session().await?;
+
+        let mut claims = stub::claims_for(&Uuid::new_v4().to_string());
+        claims["aud"] = "another-client".into();
+        let request = AuthenticateCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            token: IdToken::parse(&stub::sign(&claims))?,
+        };
+        let result = handler(&request).await;
+        assert!(matches!(result, Err(LogicError::AuthenticationError(_))));
+        Ok(())
+    }
+}
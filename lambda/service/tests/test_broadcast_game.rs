@@ -0,0 +1,57 @@
+// Only the in-memory table can stream its changes in process
+#![cfg(feature = "in_memory")]
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::errors::LogicError;
+    use notifier::INotifier;
+    use service::game_stream::handler;
+    use storage::{
+        game_table::{GameItem, PlayerItem},
+        session_table::SessionItem,
+        IDynamoDbClient,
+    };
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn sends_game_once_change_commits() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        // Wired up like an in-memory server
+        let game_table_name = config::get().game_table_name.clone();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        db.stream_to(Box::new(move |table_name, record| {
+            if table_name == game_table_name {
+                let _ = sender.send(record);
+            }
+        }));
+
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let mut game = GameItem::new(&GameItem::create_game_code(), &session_id);
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.game_id = Some(game.game_id.clone());
+        game.players
+            .push(PlayerItem::new(&session_id, &None, "Host"));
+        db.write(vec![session.save()?, game.save()?]).await?;
+
+        // The session comes through the stream too, and is skipped
+        while let Some(record) = receiver.recv().await {
+            if handler(&record).await? != "Skipped" {
+                break;
+            }
+        }
+
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_id);
+        assert_eq!(messages.len(), 1);
+        let message: serde_json::Value = serde_json::from_str(&messages[0]).unwrap();
+        assert_eq!(message["action"], "gameState");
+        assert!(messages[0].contains(&game.game_id));
+        Ok(())
+    }
+}
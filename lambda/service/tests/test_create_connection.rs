@@ -5,6 +5,7 @@ mod tests {
     use super::*;
     use chrono::Utc;
     use domain::{commands::CreateConnectionCommand, errors::LogicError};
+    use domain::values::ConnectionId;
     use service::create_connection::handler;
     use storage::{websocket_table::WebsocketItem, IDynamoDbClient};
     use uuid::Uuid;
@@ -15,7 +16,7 @@ mod tests {
         let start_time = Utc::now();
         let connection_id = Uuid::new_v4().to_string();
         let request = CreateConnectionCommand {
-            connection_id: connection_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
         };
         let result = handler(&request).await;
         assert!(result.is_ok(), "Error: {:?}", result.err());
@@ -36,7 +37,9 @@ mod tests {
         let item = WebsocketItem::new(&connection_id);
         db.write_single(item.save()?).await?;
 
-        let request = CreateConnectionCommand { connection_id };
+        let request = CreateConnectionCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+        };
         let result = handler(&request).await;
         assert!(result.is_err());
         Ok(())
@@ -4,12 +4,15 @@ mod test_setup;
 mod tests {
     use crate::test_setup;
     use chrono::Utc;
-    use domain::{commands::CreateGameCommand, errors::LogicError};
+    use domain::{commands::CreateGameCommand, errors::LogicError, events::DomainEvent};
+    use domain::values::{ConnectionId, SessionId};
     use event_publisher::IEventPublisher;
+    use metrics::{names::GAMES_CREATED, IMetrics};
     use notifier::INotifier;
     use service::create_game::handler;
     use storage::{
         game_table::{GameAction, GameItem},
+        profile_table::ProfileItem,
         session_table::{SessionAction, SessionItem},
         websocket_table::WebsocketItem,
         IDynamoDbClient,
@@ -22,8 +25,8 @@ mod tests {
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
         let request = CreateGameCommand {
-            connection_id,
-            session_id,
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+            session_id: SessionId::parse(&session_id).unwrap(),
         };
         let result = handler(&request).await;
         assert!(result.is_err());
@@ -37,11 +40,12 @@ mod tests {
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
         let session = SessionItem::new(&session_id, &connection_id);
-        db.write_single(session.save()?).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = CreateGameCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         let result = handler(&request).await;
         assert!(result.is_err());
@@ -56,21 +60,30 @@ mod tests {
 
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
-        let connection = WebsocketItem::new(&connection_id);
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
         let mut session = SessionItem::new(&session_id, &connection_id);
         session.nickname = Some("Test".to_string());
         db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = CreateGameCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
-        let game_id = handler(&request).await?;
-
-        // Notifies the connection (1 for new game, 1 for game state)
+        let game_id = metrics::scope(async {
+            let game_id = handler(&request).await?;
+
+            // Records metric
+            let metrics = metrics::get().await;
+            assert_eq!(metrics.get_count(GAMES_CREATED, &[]), 1.0);
+            Ok::<_, LogicError>(game_id)
+        })
+        .await?;
+        service::relay_outbox::handler().await?;
+
+        // Notifies the connection, the game state comes from the game stream
         let notifier = notifier::get().await;
         let messages = notifier.get_messages(&connection_id);
-        assert_eq!(messages.len(), 2);
+        assert_eq!(messages.len(), 1);
 
         // Creates game item
         let game = GameItem::from_db(&game_id, &db).await?;
@@ -78,7 +91,7 @@ mod tests {
         assert_eq!(game.modified_action, GameAction::CreateGame);
         assert_eq!(game.modified_by, session_id);
         assert!(game.mr_eleven.is_none());
-        assert_eq!(game.round_finished, false);
+        assert!(!game.round_finished);
         assert_eq!(game.version, 0);
 
         // Updates session item
@@ -89,8 +102,38 @@ mod tests {
 
         // Publishes event
         let event_publisher = event_publisher::get().await;
-        let messages = event_publisher.get_messages("RustLambda-Dev.GameCreated");
-        assert_eq!(messages.len(), 1);
+        let event = DomainEvent::GameCreated {
+            game_id: game_id.clone(),
+            session_id: session_id.clone(),
+        };
+        assert!(event_publisher.get_events().contains(&event));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn counts_game_in_profile() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.account_id = Some(account_id.clone());
+        session.nickname = Some("Test".to_string());
+        db.write(vec![connection.save()?, session.save()?]).await?;
+
+        let request = CreateGameCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        handler(&request).await?;
+
+        // Creates profile item
+        let profile = ProfileItem::from_db(&account_id, &db).await?;
+        assert_eq!(profile.games_played, 1);
+        assert_eq!(profile.version, 0);
         Ok(())
     }
 
@@ -104,11 +147,12 @@ mod tests {
         let mut session = SessionItem::new(&session_id, &connection_id);
         session.nickname = Some("Test".to_string());
         session.game_id = Some("ABCD".to_string());
-        db.write_single(session.save()?).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = CreateGameCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         handler(&request).await?;
 
@@ -4,6 +4,7 @@ mod test_setup;
 mod tests {
     use chrono::Utc;
     use domain::{commands::CreateSessionCommand, errors::LogicError};
+    use domain::values::ConnectionId;
     use notifier::INotifier;
     use service::create_session::handler;
     use storage::{
@@ -19,7 +20,9 @@ mod tests {
     async fn errors_if_connection_doesnt_exist() {
         test_setup::setup();
         let connection_id = Uuid::new_v4().to_string();
-        let request = CreateSessionCommand { connection_id };
+        let request = CreateSessionCommand {
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+        };
         let result = handler(&request).await;
         assert!(result.is_err());
     }
@@ -35,7 +38,7 @@ mod tests {
         db.write_single(connection.save()?).await?;
 
         let request = CreateSessionCommand {
-            connection_id: connection_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
         };
         let result = handler(&request).await;
 
@@ -52,7 +55,7 @@ mod tests {
         assert!(connection.modified_at > start_time);
         let session_id = match connection.session_id {
             Some(session_id) => session_id,
-            None => return Err(LogicError::GetItemError("Session not found".to_string())),
+            None => return Err(LogicError::ItemNotFound("Session not found".to_string())),
         };
 
         // Updates session item
@@ -76,7 +79,7 @@ mod tests {
         db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = CreateSessionCommand {
-            connection_id: connection_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
         };
         let result = handler(&request).await?;
         assert_eq!(result, session_id);
@@ -4,10 +4,18 @@ mod test_setup;
 mod tests {
     use crate::test_setup;
     use chrono::Utc;
-    use domain::{commands::DestroyConnectionCommand, errors::LogicError};
+    use domain::{
+        commands::{CheckSessionTimeoutCommand, DestroyConnectionCommand, ScheduledCommand},
+        errors::LogicError,
+        events::DomainEvent,
+    };
+    use domain::values::ConnectionId;
     use event_publisher::IEventPublisher;
+    use notifier::INotifier;
+    use scheduler::IScheduler;
     use service::destroy_connection::handler;
     use storage::{
+        game_table::{ConnectionStatus, GameItem, PlayerItem},
         session_table::{SessionAction, SessionItem},
         websocket_table::WebsocketItem,
         IDynamoDbClient,
@@ -18,7 +26,9 @@ mod tests {
     async fn errors_if_connection_doesnt_exist() {
         test_setup::setup();
         let connection_id = Uuid::new_v4().to_string();
-        let request = DestroyConnectionCommand { connection_id };
+        let request = DestroyConnectionCommand {
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+        };
         let result = handler(&request).await;
         assert!(result.is_err());
     }
@@ -33,7 +43,7 @@ mod tests {
         db.write_single(connection.save()?).await?;
 
         let request = DestroyConnectionCommand {
-            connection_id: connection_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
         };
         let result = handler(&request).await;
         assert!(result.is_ok(), "Error: {:?}", result.err());
@@ -57,10 +67,11 @@ mod tests {
         db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = DestroyConnectionCommand {
-            connection_id: connection_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
         };
         let result = handler(&request).await;
         assert!(result.is_ok(), "Error: {:?}", result.err());
+        service::relay_outbox::handler().await?;
 
         // Deletes connection from database
         let connection = WebsocketItem::from_db(&connection_id, &db).await;
@@ -73,9 +84,67 @@ mod tests {
 
         // Publishes event
         let event_publisher = event_publisher::get().await;
-        let messages = event_publisher.get_messages("RustLambda-Dev.Websocket");
-        assert_eq!(messages.len(), 1);
+        let event = DomainEvent::Disconnected {
+            session_id: session_id.clone(),
+        };
+        assert!(event_publisher.get_events().contains(&event));
+
+        // Schedules the timeout check for the end of the grace period
+        let command = ScheduledCommand::CheckSessionTimeout(CheckSessionTimeoutCommand {
+            session_id: session_id.clone(),
+        });
+        let jobs = scheduler::get().await.get_jobs();
+        let job = jobs.iter().find(|job| job.command == command).unwrap();
+        assert!(job.run_at > session.modified_at);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn holds_place_while_reconnecting() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_id = GameItem::create_game_code();
+        let mut game = GameItem::new(&game_id, "host");
+        let mut writes = vec![];
+        let mut connection_ids = vec![];
+        for nickname in ["Dropped", "Staying"] {
+            let connection_id = Uuid::new_v4().to_string();
+            let session_id = Uuid::new_v4().to_string();
+            let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+            let mut session = SessionItem::new(&session_id, &connection_id);
+            session.game_id = Some(game_id.clone());
+            game.players
+                .push(PlayerItem::new(&session_id, &None, nickname));
+            writes.extend([connection.save()?, session.save()?]);
+            connection_ids.push(connection_id);
+        }
+        writes.push(game.save()?);
+        db.write(writes).await?;
+
+        let request = DestroyConnectionCommand {
+            connection_id: ConnectionId::parse(&connection_ids[0])?,
+        };
+        handler(&request).await?;
+        service::relay_outbox::handler().await?;
+
+        // Holds the player's place until the deadline
+        let game = GameItem::from_db(&game_id, &db).await?;
+        let dropped = &game.players[0];
+        assert_eq!(
+            dropped.connection_status(Utc::now()),
+            ConnectionStatus::Reconnecting
+        );
+        assert!(dropped.reconnect_deadline.unwrap() > Utc::now());
+        assert_eq!(
+            game.players[1].connection_status(Utc::now()),
+            ConnectionStatus::Connected
+        );
 
+        // Leaves telling the other player to the game stream
+        let notifier = notifier::get().await;
+        assert!(notifier.get_messages(&connection_ids[1]).is_empty());
         Ok(())
     }
 }
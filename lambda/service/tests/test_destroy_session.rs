@@ -5,6 +5,8 @@ mod tests {
     use crate::test_setup;
     use domain::commands::DestroySessionCommand;
     use domain::errors::LogicError;
+    use domain::events::DomainEvent;
+    use event_publisher::IEventPublisher;
     use notifier::{self, INotifier};
     use service::destroy_session::handler;
     use storage::session_table::SessionItem;
@@ -41,6 +43,7 @@ mod tests {
         };
         let result = handler(&request).await;
         assert!(result.is_ok(), "Error: {:?}", result.err());
+        service::relay_outbox::handler().await?;
 
         // Deletes database item
         let session = SessionItem::from_db(&session_id, &db).await;
@@ -51,6 +54,12 @@ mod tests {
         let messages = notifier.get_messages(&connection_id);
         assert_eq!(messages.len(), 1);
 
+        // Publishes event
+        let event = DomainEvent::SessionExpired {
+            session_id: session_id.clone(),
+        };
+        assert!(event_publisher::get().await.get_events().contains(&event));
+
         Ok(())
     }
 
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use domain::errors::LogicError;
     use service::finish_round::handler;
@@ -19,6 +20,9 @@ mod test {
             outcome: RollResultNote::None,
             outcome_type: RollResultType::None,
             finished: false,
+            reconnect_deadline: None,
+            rounds_won: 0,
+            pools: 0,
         }];
         handler(&mut game)?;
         // No update
@@ -44,6 +48,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_2".to_string(),
@@ -59,6 +66,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
         ];
         handler(&mut game)?;
@@ -91,6 +101,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_2".to_string(),
@@ -106,6 +119,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_3".to_string(),
@@ -126,6 +142,9 @@ mod test {
                 outcome: RollResultNote::Shower,
                 outcome_type: RollResultType::Loser,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
         ];
         handler(&mut game)?;
@@ -161,6 +180,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_2".to_string(),
@@ -176,6 +198,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_3".to_string(),
@@ -191,6 +216,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_4".to_string(),
@@ -206,6 +234,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
         ];
         handler(&mut game)?;
@@ -249,6 +280,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_2".to_string(),
@@ -265,6 +299,9 @@ mod test {
                 outcome: RollResultNote::None,
                 outcome_type: RollResultType::None,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
         ];
         handler(&mut game)?;
@@ -303,6 +340,9 @@ mod test {
                 outcome: RollResultNote::FinishDrink,
                 outcome_type: RollResultType::Loser,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
             PlayerItem {
                 player_id: "player_2".to_string(),
@@ -319,6 +359,9 @@ mod test {
                 outcome: RollResultNote::Shower,
                 outcome_type: RollResultType::Loser,
                 finished: true,
+                reconnect_deadline: None,
+                rounds_won: 0,
+                pools: 0,
             },
         ];
         handler(&mut game)?;
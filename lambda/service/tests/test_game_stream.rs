@@ -0,0 +1,130 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use aws_sdk_dynamodb::types::AttributeValue;
+    use chrono::Utc;
+    use domain::errors::LogicError;
+    use notifier::INotifier;
+    use service::game_stream::handler;
+    use std::collections::HashMap;
+    use storage::{
+        game_table::{GameItem, PlayerItem},
+        session_table::SessionItem,
+        stream_record::StreamRecord,
+        IDynamoDbClient,
+    };
+    use uuid::Uuid;
+
+    // The item as the table's stream would report it
+    fn image(game: &GameItem) -> Result<HashMap<String, AttributeValue>, LogicError> {
+        Ok(game.save()?.put.unwrap().item)
+    }
+
+    /// Saves a session for each player, returning the game and their
+    /// connections.
+    async fn setup_game(nicknames: &[&str]) -> Result<(GameItem, Vec<String>), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let mut game = GameItem::new(&GameItem::create_game_code(), "host");
+        let mut connection_ids = vec![];
+        for nickname in nicknames {
+            let connection_id = Uuid::new_v4().to_string();
+            let session_id = Uuid::new_v4().to_string();
+            let mut session = SessionItem::new(&session_id, &connection_id);
+            session.game_id = Some(game.game_id.clone());
+            db.write_single(session.save()?).await?;
+            game.players
+                .push(PlayerItem::new(&session_id, &None, nickname));
+            connection_ids.push(connection_id);
+        }
+        Ok((game, connection_ids))
+    }
+
+    #[tokio::test]
+    async fn sends_new_game() -> Result<(), LogicError> {
+        let (game, connection_ids) = setup_game(&["Host"]).await?;
+
+        let record = StreamRecord {
+            old_image: None,
+            new_image: Some(image(&game)?),
+        };
+        let result = handler(&record).await;
+        assert!(result.is_ok(), "Error: {:?}", result.err());
+
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_ids[0]);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("gameState"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tells_other_players_while_reconnecting() -> Result<(), LogicError> {
+        let (old_game, connection_ids) = setup_game(&["Dropped", "Staying"]).await?;
+        let mut game = old_game.clone();
+        game.players[0].reconnect_deadline = Some(Utc::now() + chrono::TimeDelta::seconds(30));
+        game.version += 1;
+
+        let record = StreamRecord {
+            old_image: Some(image(&old_game)?),
+            new_image: Some(image(&game)?),
+        };
+        handler(&record).await?;
+
+        // Only the connected player hears about it
+        let notifier = notifier::get().await;
+        assert!(notifier.get_messages(&connection_ids[0]).is_empty());
+        let messages = notifier.get_messages(&connection_ids[1]);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("RECONNECTING"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skips_unchanged_version() -> Result<(), LogicError> {
+        let (game, connection_ids) = setup_game(&["Host"]).await?;
+
+        let record = StreamRecord {
+            old_image: Some(image(&game)?),
+            new_image: Some(image(&game)?),
+        };
+        handler(&record).await?;
+
+        let notifier = notifier::get().await;
+        assert!(notifier.get_messages(&connection_ids[0]).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skips_deleted_game() -> Result<(), LogicError> {
+        let (game, connection_ids) = setup_game(&["Host"]).await?;
+
+        let record = StreamRecord {
+            old_image: Some(image(&game)?),
+            new_image: None,
+        };
+        handler(&record).await?;
+
+        let notifier = notifier::get().await;
+        assert!(notifier.get_messages(&connection_ids[0]).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skips_sessions() -> Result<(), LogicError> {
+        test_setup::setup();
+        let connection_id = Uuid::new_v4().to_string();
+        let session = SessionItem::new(&Uuid::new_v4().to_string(), &connection_id);
+
+        let record = StreamRecord {
+            old_image: None,
+            new_image: Some(session.save()?.put.unwrap().item),
+        };
+        let result = handler(&record).await;
+        assert!(result.is_ok(), "Error: {:?}", result.err());
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::commands::{GetLeaderboardCommand, QueryLeaderboardCommand};
+    use domain::errors::LogicError;
+    use domain::values::{ConnectionId, GameCode, LeaderboardStat, Page};
+    use notifier::{self, INotifier};
+    use serde_json::Value;
+    use service::get_leaderboard::{handler, query};
+    use storage::game_table::GameItem;
+    use storage::leaderboard_table::{LeaderboardItem, LeaderboardScope};
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn notifies_leaderboard() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let game_id = GameItem::create_game_code();
+        let game = GameItem::new(&game_id, &Uuid::new_v4().to_string());
+        db.write_single(game.save()?).await?;
+        let scope = LeaderboardScope::Group(game.instance_id);
+        let account_id = Uuid::new_v4().to_string();
+        let entry = LeaderboardItem::new(&scope, LeaderboardStat::Wins, &account_id, "Alice", 3);
+        db.write_single(entry.save()?).await?;
+
+        let request = GetLeaderboardCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            game_id: Some(GameCode::parse(&game_id)?),
+            page: Page::parse(None, None)?,
+            stat: LeaderboardStat::Wins,
+        };
+        let result = handler(&request).await;
+
+        // Returns OK
+        assert!(result.is_ok(), "Error: {:?}", result.err());
+
+        // Notifies the connection
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_id);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("getLeaderboard"));
+        assert!(messages[0].contains("Alice"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn paginates_by_rank() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_id = GameItem::create_game_code();
+        let game = GameItem::new(&game_id, &Uuid::new_v4().to_string());
+        db.write_single(game.save()?).await?;
+        let scope = LeaderboardScope::Group(game.instance_id);
+        for score in 1..=5 {
+            let account_id = Uuid::new_v4().to_string();
+            let nickname = format!("P{score}");
+            let entry = LeaderboardItem::new(
+                &scope,
+                LeaderboardStat::Pools,
+                &account_id,
+                &nickname,
+                score,
+            );
+            db.write_single(entry.save()?).await?;
+        }
+
+        let request = QueryLeaderboardCommand {
+            game_id: Some(GameCode::parse(&game_id)?),
+            page: Page::parse(Some(2), Some(2))?,
+            stat: LeaderboardStat::Pools,
+        };
+        let result = query(&request).await?;
+
+        let leaderboard: Value = serde_json::from_str(&result).unwrap();
+        let entries = leaderboard["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["rank"], 3);
+        assert_eq!(entries[0]["score"], 3);
+        assert_eq!(entries[1]["nickname"], "P2");
+        assert_eq!(leaderboard["nextOffset"], 4);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn starts_afresh_when_code_is_reused() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_id = GameItem::create_game_code();
+        let old_game = GameItem::new(&game_id, &Uuid::new_v4().to_string());
+        let scope = LeaderboardScope::Group(old_game.instance_id);
+        let account_id = Uuid::new_v4().to_string();
+        let entry = LeaderboardItem::new(&scope, LeaderboardStat::Wins, &account_id, "Alice", 3);
+        db.write_single(entry.save()?).await?;
+        let game = GameItem::new(&game_id, &Uuid::new_v4().to_string());
+        db.write_single(game.save()?).await?;
+
+        let request = QueryLeaderboardCommand {
+            game_id: Some(GameCode::parse(&game_id)?),
+            page: Page::parse(None, None)?,
+            stat: LeaderboardStat::Wins,
+        };
+        let result = query(&request).await?;
+
+        let leaderboard: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(leaderboard["entries"].as_array().unwrap().len(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn returns_empty_leaderboard_for_new_group() -> Result<(), LogicError> {
+        test_setup::setup();
+        let request = QueryLeaderboardCommand {
+            game_id: Some(GameCode::parse("ZZ99")?),
+            page: Page::parse(None, None)?,
+            stat: LeaderboardStat::WinStreak,
+        };
+        let result = query(&request).await?;
+
+        let leaderboard: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(leaderboard["entries"].as_array().unwrap().len(), 0);
+        assert!(leaderboard["nextOffset"].is_null());
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::commands::{GetAccountProfileCommand, GetProfileCommand};
+    use domain::errors::LogicError;
+    use domain::values::{AccountId, ConnectionId, SessionId};
+    use notifier::{self, INotifier};
+    use serde_json::Value;
+    use service::get_profile::{get_for_account, handler};
+    use storage::profile_table::ProfileItem;
+    use storage::session_table::SessionItem;
+    use storage::websocket_table::WebsocketItem;
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn errors_if_session_is_not_authenticated() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let session = SessionItem::new(&session_id, &connection_id);
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
+
+        let request = GetProfileCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        let result = handler(&request).await;
+        assert_eq!(result, Err(LogicError::NotAllowed));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn notifies_profile() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.account_id = Some(account_id.clone());
+        let mut profile = ProfileItem::new(&account_id);
+        profile.rounds_won = 4;
+        profile.outcome_counts.insert("POOL".to_string(), 2);
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?, profile.save()?])
+            .await?;
+
+        let request = GetProfileCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        let result = handler(&request).await;
+
+        // Returns OK
+        assert!(result.is_ok(), "Error: {:?}", result.err());
+
+        // Notifies the connection
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_id);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("getProfile"));
+        assert!(messages[0].contains("roundsWon"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn returns_empty_profile_for_new_account() -> Result<(), LogicError> {
+        test_setup::setup();
+        let account_id = Uuid::new_v4().to_string();
+
+        let request = GetAccountProfileCommand {
+            account_id: AccountId::parse(&account_id)?,
+        };
+        let result = get_for_account(&request).await?;
+
+        let profile: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(profile["accountId"], account_id);
+        assert_eq!(profile["gamesPlayed"], 0);
+        assert_eq!(profile["roundsWon"], 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::errors::LogicError;
+    use serde_json::Value;
+    use service::health::handler;
+
+    #[tokio::test]
+    async fn reports_reachable_tables() -> Result<(), LogicError> {
+        test_setup::setup();
+
+        let health = handler().await?;
+
+        assert!(health.healthy);
+        let view: Value = serde_json::from_str(&health.body).unwrap();
+        assert_eq!(view["status"], "ok");
+        let tables = view["config"]["tables"].as_array().unwrap();
+        assert!(tables.contains(&Value::from("GAME")));
+        assert!(view["config"].get("cognitoClientId").is_none());
+        let dependencies = view["dependencies"].as_array().unwrap();
+        assert_eq!(dependencies.len(), 6);
+        assert!(dependencies.iter().all(|d| d["reachable"] == true));
+        Ok(())
+    }
+}
@@ -6,8 +6,10 @@ mod tests {
     use chrono::Utc;
     use domain::commands::LeaveGameCommand;
     use domain::errors::LogicError;
+    use domain::events::DomainEvent;
+    use event_publisher::IEventPublisher;
     use service::leave_game::handler;
-    use storage::game_table::{GameAction, GameItem, RollResultType};
+    use storage::game_table::{GameAction, GameItem, RollResultType, RoundItem};
     use storage::IDynamoDbClient;
     use storage::{
         game_table::{PlayerItem, RollResultNote},
@@ -48,11 +50,19 @@ mod tests {
         };
         let result = handler(&request).await;
         assert!(result.is_ok(), "Error: {:?}", result.err());
+        service::relay_outbox::handler().await?;
 
         // Deletes database item
         let game = GameItem::from_db(&game_id, &db).await;
         assert!(game.is_err());
 
+        // Publishes event
+        let event = DomainEvent::PlayerLeft {
+            game_id: game_id.clone(),
+            session_id: session_id.clone(),
+        };
+        assert!(event_publisher::get().await.get_events().contains(&event));
+
         Ok(())
     }
 
@@ -78,6 +88,9 @@ mod tests {
             outcome: RollResultNote::None,
             outcome_type: RollResultType::None,
             finished: true,
+            reconnect_deadline: None,
+            rounds_won: 0,
+            pools: 0,
         });
         let session1 = SessionItem::new(&session_id1, &connection_id);
         let session2 = SessionItem::new(&session_id2, &connection_id);
@@ -94,10 +107,53 @@ mod tests {
         // Updates database item
         let game = GameItem::from_db(&game_id, &db).await?;
         assert!(game.round_finished);
+        assert_eq!(game.players[0].outcome_type, RollResultType::Winner);
+        assert_eq!(game.players[0].win_counter, 1);
         assert_eq!(game.version, 1);
         assert_eq!(game.modified_action, GameAction::LeaveGame);
         assert!(game.modified_at > start_time);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn records_no_round_if_left_in_a_tie() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_id = Uuid::new_v4().to_string();
+        let leaving_id = Uuid::new_v4().to_string();
+        let connection_id = Uuid::new_v4().to_string();
+        let mut game = GameItem::new(&game_id, &leaving_id);
+        game.players
+            .push(PlayerItem::new(&leaving_id, &None, "Leaving"));
+        // Three players on the same score tie and roll again
+        for nickname in ["One", "Two", "Three"] {
+            let mut player = PlayerItem::new(&Uuid::new_v4().to_string(), &None, nickname);
+            player.finished = true;
+            game.players.push(player);
+        }
+        let session = SessionItem::new(&leaving_id, &connection_id);
+        db.write(vec![session.save()?, game.save()?]).await?;
+
+        let request = LeaveGameCommand {
+            game_id: game_id.clone(),
+            session_id: leaving_id.clone(),
+        };
+        handler(&request).await?;
+        service::relay_outbox::handler().await?;
+
+        let game = GameItem::from_db(&game_id, &db).await?;
+        assert!(!game.round_finished);
+        assert_eq!(game.round_count, 0);
+        let rounds = RoundItem::from_db_batch(&game.instance_id, &[1], &db).await?;
+        assert!(rounds.is_empty());
+        assert!(game.players.iter().all(|p| !p.finished));
+        let events = event_publisher::get().await.get_events();
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            DomainEvent::RoundFinished { game_id: id, .. } if *id == game_id
+        )));
+        Ok(())
+    }
 }
@@ -1,16 +1,21 @@
 mod test_setup;
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use crate::test_setup;
     use chrono::Utc;
     use domain::commands::NewRoundCommand;
     use domain::errors::LogicError;
+    use domain::events::DomainEvent;
+    use domain::values::{ConnectionId, SessionId};
+    use event_publisher::IEventPublisher;
     use notifier::{self, INotifier};
     use service::new_round::handler;
     use std::vec;
     use storage::game_table::{GameAction, GameItem, PlayerItem};
     use storage::session_table::SessionItem;
+    use storage::websocket_table::WebsocketItem;
     use storage::IDynamoDbClient;
     use uuid::Uuid;
 
@@ -20,8 +25,8 @@ mod tests {
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
         let request = NewRoundCommand {
-            connection_id,
-            session_id,
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+            session_id: SessionId::parse(&session_id).unwrap(),
         };
         let result = handler(&request).await;
         assert!(result.is_err());
@@ -35,11 +40,12 @@ mod tests {
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
         let session = SessionItem::new(&session_id, &connection_id);
-        db.write_single(session.save()?).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = NewRoundCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         handler(&request).await?;
 
@@ -62,11 +68,13 @@ mod tests {
         session.game_id = Some(game_id.clone());
         let mut game = GameItem::new(&game_id, &session_id);
         game.round_finished = false;
-        db.write(vec![session.save()?, game.save()?]).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?, game.save()?])
+            .await?;
 
         let request = NewRoundCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         handler(&request).await?;
 
@@ -94,18 +102,21 @@ mod tests {
         let player = PlayerItem::new(&session.session_id, &session.account_id, &nickname);
         game.players.push(player);
         game.round_finished = true;
-        db.write(vec![session.save()?, game.save()?]).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?, game.save()?])
+            .await?;
 
         let request = NewRoundCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         handler(&request).await?;
+        service::relay_outbox::handler().await?;
 
-        // Notifies the connection
+        // Leaves telling the players to the game stream
         let notifier = notifier::get().await;
         let messages = notifier.get_messages(&connection_id);
-        assert_eq!(messages.len(), 1);
+        assert_eq!(messages.len(), 0);
 
         // Updates game item
         let game = GameItem::from_db(&game_id, &db).await?;
@@ -115,6 +126,12 @@ mod tests {
         assert_eq!(game.round_finished, false);
         assert_eq!(game.version, 1);
 
+        // Publishes event
+        let event = DomainEvent::NewRoundStarted {
+            game_id: game_id.clone(),
+        };
+        assert!(event_publisher::get().await.get_events().contains(&event));
+
         Ok(())
     }
 }
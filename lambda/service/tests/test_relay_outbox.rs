@@ -0,0 +1,63 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::errors::LogicError;
+    use notifier::INotifier;
+    use service::relay_outbox::relay;
+    use storage::game_table::GameItem;
+    use storage::{
+        outbox_table::{OutboxEntry, OutboxItem},
+        IDynamoDbClient,
+    };
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn delivers_notification() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let item = OutboxItem::new(OutboxEntry::Notification {
+            connection_id: connection_id.clone(),
+            data: "hello".to_string(),
+        });
+        db.write_single(item.save()?).await?;
+
+        let result = relay(&item).await;
+        assert!(result.is_ok(), "Error: {:?}", result.err());
+
+        // Sends the message
+        let messages = notifier::get().await.get_messages(&connection_id);
+        assert_eq!(messages, vec!["hello".to_string()]);
+
+        // Removes the entry
+        let pending = OutboxItem::pending(&db).await?;
+        assert!(pending.iter().all(|p| p.id != item.id));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn writes_nothing_when_a_condition_fails() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let game_id = GameItem::create_game_code();
+        let mut game = GameItem::new(&game_id, "host");
+        db.write_single(game.save()?).await?;
+        // Saved as though another write got there first
+        game.version += 2;
+        let item = OutboxItem::new(OutboxEntry::Notification {
+            connection_id: Uuid::new_v4().to_string(),
+            data: "hello".to_string(),
+        });
+
+        let result = db.write(vec![item.save()?, game.save()?]).await;
+
+        assert!(matches!(result, Err(LogicError::ConditionalCheckFailed(_))));
+        let pending = OutboxItem::pending(&db).await?;
+        assert!(pending.iter().all(|p| p.id != item.id));
+        Ok(())
+    }
+}
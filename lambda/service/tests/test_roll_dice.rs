@@ -6,12 +6,20 @@ mod test {
     use chrono::Utc;
     use domain::commands::RollDiceCommand;
     use domain::errors::LogicError;
+    use domain::events::DomainEvent;
+    use domain::values::{ConnectionId, LeaderboardStat, SessionId};
+    use event_publisher::IEventPublisher;
+    use metrics::names::{DYNAMODB_LATENCY, ROLL_OUTCOMES, ROUNDS_FINISHED};
+    use metrics::IMetrics;
     use notifier::{self, INotifier};
     use rstest::rstest;
     use service::roll_dice::{calculate_individual_result, handler, roll_dice};
     use std::vec;
-    use storage::game_table::{DiceItem, DiceType, GameAction, GameItem, PlayerItem, RollItem, RollResultItem, RollResultNote, RollResultType};
+    use storage::game_table::{DiceItem, DiceType, GameAction, GameItem, PlayerItem, RollItem, RollResultItem, RollResultNote, RollResultType, RoundItem};
+    use storage::leaderboard_table::{LeaderboardItem, LeaderboardScope};
+    use storage::profile_table::ProfileItem;
     use storage::session_table::SessionItem;
+    use storage::websocket_table::WebsocketItem;
     use storage::IDynamoDbClient;
     use uuid::Uuid;
 
@@ -24,13 +32,42 @@ mod test {
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
         let request = RollDiceCommand {
-            connection_id,
-            session_id,
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+            session_id: SessionId::parse(&session_id).unwrap(),
         };
         let result = handler(&request).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn errors_if_session_belongs_to_another_connection() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let other_connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let other_session_id = Uuid::new_v4().to_string();
+        let session = SessionItem::new(&session_id, &connection_id);
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        let other_connection =
+            WebsocketItem::new_with_session(&other_connection_id, &other_session_id);
+        db.write(vec![
+            connection.save()?,
+            other_connection.save()?,
+            session.save()?,
+        ])
+        .await?;
+
+        let request = RollDiceCommand {
+            connection_id: ConnectionId::parse(&other_connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        let result = handler(&request).await;
+        assert_eq!(result, Err(LogicError::NotAllowed));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn does_nothing_if_game_doesnt_exist() -> Result<(), LogicError> {
         test_setup::setup();
@@ -39,11 +76,12 @@ mod test {
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
         let session = SessionItem::new(&session_id, &connection_id);
-        db.write_single(session.save()?).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
 
         let request = RollDiceCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         handler(&request).await?;
 
@@ -70,11 +108,13 @@ mod test {
         player.finished = true;
         game.players.push(player);
 
-        db.write(vec![session.save()?, game.save()?]).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?, game.save()?])
+            .await?;
 
         let request = RollDiceCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         handler(&request).await?;
 
@@ -102,26 +142,44 @@ mod test {
         let player = PlayerItem::new(&session.session_id, &session.account_id, &nickname);
         game.players.push(player);
         game.round_finished = false;
-        db.write(vec![session.save()?, game.save()?]).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?, game.save()?])
+            .await?;
 
         let request = RollDiceCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
         };
-        handler(&request).await?;
+        metrics::scope(async {
+            handler(&request).await?;
 
-        // Notifies the connection
+            // Records metrics
+            let metrics = metrics::get().await;
+            assert_eq!(metrics.get_count(ROUNDS_FINISHED, &[]), 1.0);
+            assert_eq!(metrics.get_count(ROLL_OUTCOMES, &[("Note", "WINNER")]), 1.0);
+            let latencies = metrics.get_data(DYNAMODB_LATENCY);
+            assert!(latencies.iter().any(|d| d.has_dimensions(&[("Operation", "write")])));
+            Ok::<_, LogicError>(())
+        })
+        .await?;
+        service::relay_outbox::handler().await?;
+
+        // Leaves telling the players to the game stream
         let notifier = notifier::get().await;
         let messages = notifier.get_messages(&connection_id);
-        assert_eq!(messages.len(), 1);
+        assert_eq!(messages.len(), 0);
 
         // Updates game item
         let game = GameItem::from_db(&game_id, &db).await?;
         assert!(game.modified_at > start_time);
         assert_eq!(game.modified_action, GameAction::RollDice);
         assert_eq!(game.modified_by, session_id);
-        assert_eq!(game.round_finished, true);
+        assert!(game.round_finished);
         assert_eq!(game.version, 1);
+        assert_eq!(game.round_count, 1);
+        let rounds = RoundItem::from_db_batch(&game.instance_id, &[1], &db).await?;
+        assert_eq!(rounds.len(), 1);
+        assert_eq!(rounds[0].players[0].rolls.len(), 1);
         let rolls = &game.players[0].rolls;
         assert_eq!(rolls.len(), 1);
         let dice = &rolls[0].dice;
@@ -129,6 +187,123 @@ mod test {
         assert_eq!(dice[0].value, 1);
         assert_eq!(dice[1].value, 2);
 
+        // Publishes events
+        let events = event_publisher::get().await.get_events();
+        assert!(events.contains(&DomainEvent::DiceRolled {
+            game_id: game_id.clone(),
+            session_id: session_id.clone(),
+            dice: vec![1, 2],
+            turn_finished: true,
+        }));
+        assert!(events.contains(&DomainEvent::RoundFinished {
+            game_id: game_id.clone(),
+            round_id: 1,
+            mr_eleven: None,
+        }));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn updates_profile_when_round_finishes() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let game_id = Uuid::new_v4().to_string();
+        let nickname = "AVERAGE_JOE".to_string();
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.account_id = Some(account_id.clone());
+        session.game_id = Some(game_id.clone());
+        session.nickname = Some(nickname.clone());
+        let mut game = GameItem::new(&game_id, &session_id);
+        let mut player = PlayerItem::new(&session.session_id, &session.account_id, &nickname);
+        player.win_counter = 2;
+        game.players.push(player);
+        game.round_finished = false;
+        let mut profile = ProfileItem::new(&account_id);
+        profile.rounds_played = 2;
+        profile.rounds_won = 2;
+        profile.longest_win_streak = 2;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![
+            connection.save()?,
+            session.save()?,
+            game.save()?,
+            profile.save()?,
+        ])
+        .await?;
+
+        let request = RollDiceCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        handler(&request).await?;
+
+        // Updates profile item
+        let profile = ProfileItem::from_db(&account_id, &db).await?;
+        assert_eq!(profile.version, 1);
+        assert_eq!(profile.rounds_played, 3);
+        assert_eq!(profile.rounds_won, 3);
+        assert_eq!(profile.longest_win_streak, 3);
+        assert_eq!(profile.times_mr_eleven, 0);
+        assert_eq!(profile.outcome_counts.get("WINNER"), Some(&1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn updates_leaderboards_when_round_finishes() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let game_id = Uuid::new_v4().to_string();
+        let nickname = "AVERAGE_JOE".to_string();
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.account_id = Some(account_id.clone());
+        session.game_id = Some(game_id.clone());
+        session.nickname = Some(nickname.clone());
+        let mut game = GameItem::new(&game_id, &session_id);
+        let player = PlayerItem::new(&session.session_id, &session.account_id, &nickname);
+        game.players.push(player);
+        game.round_finished = false;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?, game.save()?])
+            .await?;
+
+        let request = RollDiceCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        handler(&request).await?;
+
+        // Leaves the boards to the relay
+        let game = GameItem::from_db(&game_id, &db).await?;
+        let scope = LeaderboardScope::Group(game.instance_id.clone());
+        let board_id = LeaderboardItem::board_id(&scope, LeaderboardStat::Wins);
+        assert!(LeaderboardItem::top(&board_id, &db).await?.is_empty());
+        assert_eq!(game.players[0].rounds_won, 1);
+        service::relay_outbox::handler().await?;
+
+        // Updates the group boards
+        let board = LeaderboardItem::top(&board_id, &db).await?;
+        assert_eq!(board.len(), 1);
+        assert_eq!(board[0].account_id, account_id);
+        assert_eq!(board[0].nickname, nickname);
+        assert_eq!(board[0].score, 1);
+        assert!(board[0].expires_at.is_some());
+        let board_id = LeaderboardItem::board_id(&scope, LeaderboardStat::Pools);
+        assert!(LeaderboardItem::top(&board_id, &db).await?.is_empty());
+
+        // Updates the global board
+        let board_id = LeaderboardItem::board_id(&LeaderboardScope::Global, LeaderboardStat::Wins);
+        let entry = LeaderboardItem::from_db(&board_id, &account_id, &db).await?;
+        assert_eq!(entry.score, 1);
+        assert!(entry.expires_at.is_none());
         Ok(())
     }
 
@@ -160,8 +335,8 @@ mod test {
         let name = "name";
         let roll = roll_dice(&prev_rolls, wins, name);
         assert_eq!(roll.dice.len(), expected.len());
-        for i in 0..expected.len() {
-            assert_eq!(roll.dice[i].dice_type, expected[i]);
+        for (dice, expected) in roll.dice.iter().zip(expected.iter()) {
+            assert_eq!(dice.dice_type, *expected);
         }
     }
 
@@ -174,10 +349,10 @@ mod test {
         ],
     )]
     fn uses_special_names(#[case] name: &str, #[case] expected: Vec<DiceItem>) {
-        let roll = roll_dice(&vec![], 0, name);
+        let roll = roll_dice(&[], 0, name);
         assert_eq!(roll.dice.len(), expected.len());
-        for i in 0..expected.len() {
-            assert_eq!(roll.dice[i], expected[i]);
+        for (dice, expected) in roll.dice.iter().zip(expected.iter()) {
+            assert_eq!(dice, expected);
         }
     }
 
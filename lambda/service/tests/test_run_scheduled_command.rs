@@ -0,0 +1,41 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use chrono::Utc;
+    use domain::{
+        commands::{CheckSessionTimeoutCommand, ScheduledCommand},
+        errors::LogicError,
+    };
+    use service::run_scheduled_command::handler;
+    use storage::{
+        session_table::{SessionAction, SessionItem},
+        IDynamoDbClient,
+    };
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn runs_session_timeout_check() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.modified_action = SessionAction::PendingTimeout;
+        session.modified_at = Utc::now() - chrono::Duration::seconds(31);
+        db.write_single(session.save()?).await?;
+
+        let command = ScheduledCommand::CheckSessionTimeout(CheckSessionTimeoutCommand {
+            session_id: session_id.clone(),
+        });
+        let result = handler(&command).await;
+        assert!(result.is_ok(), "Error: {:?}", result.err());
+
+        // Deletes database item
+        let session = SessionItem::from_db(&session_id, &db).await;
+        assert!(session.is_err());
+        Ok(())
+    }
+}
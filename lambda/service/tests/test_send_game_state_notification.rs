@@ -0,0 +1,131 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use aws_sdk_dynamodb::types::{AttributeValue, Put, TransactWriteItem};
+    use domain::errors::LogicError;
+    use notifier::INotifier;
+    use service::send_game_state_notification::{send, Delivery};
+    use storage::{
+        game_table::{GameItem, PlayerItem},
+        session_table::SessionItem,
+        websocket_table::WebsocketItem,
+        IDynamoDbClient,
+    };
+    use uuid::Uuid;
+
+    /// Saves the game and a connected session for each player, returning
+    /// their connections.
+    async fn setup_game(nicknames: &[&str]) -> Result<(GameItem, Vec<String>), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let mut game = GameItem::new(&GameItem::create_game_code(), "host");
+        let mut writes = vec![];
+        let mut connection_ids = vec![];
+        for nickname in nicknames {
+            let connection_id = Uuid::new_v4().to_string();
+            let session_id = Uuid::new_v4().to_string();
+            let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+            let mut session = SessionItem::new(&session_id, &connection_id);
+            session.game_id = Some(game.game_id.clone());
+            game.players
+                .push(PlayerItem::new(&session_id, &None, nickname));
+            writes.extend([connection.save()?, session.save()?]);
+            connection_ids.push(connection_id);
+        }
+        writes.push(game.save()?);
+        db.write(writes).await?;
+        Ok((game, connection_ids))
+    }
+
+    #[tokio::test]
+    async fn sends_to_every_player() -> Result<(), LogicError> {
+        let (game, connection_ids) = setup_game(&["One", "Two", "Three"]).await?;
+
+        let recipients = send(&game).await?;
+
+        assert_eq!(recipients.len(), 3);
+        assert!(recipients.iter().all(|r| r.delivery == Delivery::Sent));
+        let notifier = notifier::get().await;
+        for connection_id in connection_ids {
+            let messages = notifier.get_messages(&connection_id);
+            assert_eq!(messages.len(), 1);
+            assert!(messages[0].contains("gameState"));
+        }
+        Ok(())
+    }
+
+    // Only the in-memory notifier can close a connection behind our back
+    #[cfg(feature = "in_memory")]
+    #[tokio::test]
+    async fn carries_on_past_gone_connection() -> Result<(), LogicError> {
+        use chrono::Utc;
+        use storage::{game_table::ConnectionStatus, session_table::SessionAction};
+
+        let (game, connection_ids) = setup_game(&["Gone", "Staying"]).await?;
+        let notifier = notifier::get().await;
+        notifier.close(&connection_ids[0]);
+
+        let recipients = send(&game).await?;
+
+        assert_eq!(recipients[0].delivery, Delivery::Gone);
+        assert_eq!(recipients[1].delivery, Delivery::Sent);
+        assert_eq!(notifier.get_messages(&connection_ids[1]).len(), 1);
+
+        // Drops the connection as though it had disconnected
+        let db = storage::get().await;
+        let connection = WebsocketItem::from_db(&connection_ids[0], &db).await;
+        assert!(connection.is_err());
+        let session = SessionItem::from_db(&recipients[0].session_id, &db).await?;
+        assert_eq!(session.modified_action, SessionAction::PendingTimeout);
+        let game = GameItem::from_db(&game.game_id, &db).await?;
+        assert_eq!(
+            game.players[0].connection_status(Utc::now()),
+            ConnectionStatus::Reconnecting
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_missing_session() -> Result<(), LogicError> {
+        let (mut game, connection_ids) = setup_game(&["Staying"]).await?;
+        let session_id = Uuid::new_v4().to_string();
+        game.players
+            .push(PlayerItem::new(&session_id, &None, "Missing"));
+
+        let recipients = send(&game).await?;
+
+        assert_eq!(recipients[0].delivery, Delivery::Sent);
+        assert_eq!(recipients[1].session_id, session_id);
+        assert!(matches!(recipients[1].delivery, Delivery::Failed(_)));
+        let notifier = notifier::get().await;
+        assert_eq!(notifier.get_messages(&connection_ids[0]).len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_unreadable_session() -> Result<(), LogicError> {
+        let (mut game, connection_ids) = setup_game(&["Staying"]).await?;
+        let session_id = Uuid::new_v4().to_string();
+        game.players
+            .push(PlayerItem::new(&session_id, &None, "Unreadable"));
+        let put = Put::builder()
+            .table_name(config::get().game_table_name.clone())
+            .item("id", AttributeValue::S(session_id.clone()))
+            .build()
+            .unwrap();
+        let db = storage::get().await;
+        db.write(vec![TransactWriteItem::builder().put(put).build()])
+            .await?;
+
+        let recipients = send(&game).await?;
+
+        assert_eq!(recipients[0].delivery, Delivery::Sent);
+        assert!(matches!(recipients[1].delivery, Delivery::Failed(_)));
+        let notifier = notifier::get().await;
+        assert_eq!(notifier.get_messages(&connection_ids[0]).len(), 1);
+        Ok(())
+    }
+}
@@ -6,9 +6,13 @@ mod tests {
     use chrono::Utc;
     use domain::commands::SetNicknameCommand;
     use domain::errors::LogicError;
+    use domain::events::DomainEvent;
+    use domain::values::{AccountId, ConnectionId, Nickname, SessionId};
+    use event_publisher::IEventPublisher;
     use notifier::{self, INotifier};
-    use service::set_nickname::handler;
+    use service::set_nickname::{handler, reject};
     use storage::session_table::{SessionAction, SessionItem};
+    use storage::websocket_table::WebsocketItem;
     use storage::IDynamoDbClient;
     use uuid::Uuid;
 
@@ -18,48 +22,57 @@ mod tests {
         let nickname = "nickname".to_string();
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
-        let account_id = None;
         let request = SetNicknameCommand {
-            account_id,
-            connection_id,
-            session_id,
-            nickname,
+            account_id: None,
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+            session_id: SessionId::parse(&session_id).unwrap(),
+            nickname: Nickname::parse(&nickname).unwrap(),
         };
         let result = handler(&request).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn aborts_if_nickname_is_invalid() -> Result<(), LogicError> {
+    async fn rejects_invalid_nickname() -> Result<(), LogicError> {
         test_setup::setup();
-        let db = storage::get().await;
-
         let connection_id = Uuid::new_v4().to_string();
-        let session_id = Uuid::new_v4().to_string();
-        let session = SessionItem::new(&session_id, &connection_id);
-
-        db.write_single(session.save()?).await?;
+        let error = Nickname::parse("1").unwrap_err();
+        assert_eq!(error.field, "nickname");
 
-        let nickname = "1".to_string();
-        let request = SetNicknameCommand {
-            account_id: None,
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
-            nickname: nickname.clone(),
-        };
-        let result = handler(&request).await;
+        let result = reject(&ConnectionId::parse(&connection_id)?, &error).await;
 
         // Returns OK
         assert!(result.is_ok(), "Error: {:?}", result.err());
 
-        // Notifies the connection
+        // Notifies the connection with the reason
         let notifier = notifier::get().await;
         let messages = notifier.get_messages(&connection_id);
         assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains(&error.reason));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stores_normalised_nickname() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let session = SessionItem::new(&session_id, &connection_id);
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
+
+        let request = SetNicknameCommand {
+            account_id: None,
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            nickname: Nickname::parse("  Jose\u{0301}   Smith ")?,
+        };
+        handler(&request).await?;
 
-        // Does not update database tables
         let session = SessionItem::from_db(&session_id, &db).await?;
-        assert!(session.nickname.is_none());
+        assert_eq!(session.nickname.unwrap(), "Jos\u{00E9} Smith");
         Ok(())
     }
 
@@ -72,21 +85,24 @@ mod tests {
         let account_id = Uuid::new_v4().to_string();
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
-        let session = SessionItem::new(&session_id, &connection_id);
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.account_id = Some(account_id.clone());
 
-        db.write_single(session.save()?).await?;
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
 
         let nickname = "nickname".to_string();
         let request = SetNicknameCommand {
-            account_id: Some(account_id.clone()),
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
-            nickname: nickname.clone(),
+            account_id: Some(AccountId::parse(&account_id)?),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            nickname: Nickname::parse(&nickname)?,
         };
         let result = handler(&request).await;
 
         // Returns OK
         assert!(result.is_ok(), "Error: {:?}", result.err());
+        service::relay_outbox::handler().await?;
 
         // Notifies the connection
         let notifier = notifier::get().await;
@@ -101,6 +117,43 @@ mod tests {
         assert_eq!(session.nickname.unwrap(), nickname);
         assert!(session.modified_at > start_time);
         assert_eq!(session.modified_action, SessionAction::SetNickname);
+
+        // Publishes event
+        let event = DomainEvent::NicknameChanged {
+            session_id: session_id.clone(),
+            nickname: nickname.clone(),
+        };
+        assert!(event_publisher::get().await.get_events().contains(&event));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_account_id_is_not_authenticated() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let spoofed_account_id = Uuid::new_v4().to_string();
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.account_id = Some(account_id.clone());
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
+
+        let request = SetNicknameCommand {
+            account_id: Some(AccountId::parse(&spoofed_account_id)?),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            session_id: SessionId::parse(&session_id)?,
+            nickname: Nickname::parse("nickname")?,
+        };
+        let result = handler(&request).await;
+        assert_eq!(result, Err(LogicError::NotAllowed));
+
+        // Does not update database tables
+        let session = SessionItem::from_db(&session_id, &db).await?;
+        assert_eq!(session.account_id.unwrap(), account_id);
+        assert!(session.nickname.is_none());
         Ok(())
     }
 }
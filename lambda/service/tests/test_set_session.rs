@@ -6,8 +6,11 @@ mod tests {
     use chrono::Utc;
     use domain::commands::SetSessionCommand;
     use domain::errors::LogicError;
+    use domain::values::{ConnectionId, ReconnectToken, SessionId};
     use notifier::{self, INotifier};
+    use service::reconnect_token;
     use service::set_session::handler;
+    use storage::game_table::{ConnectionStatus, GameItem, PlayerItem};
     use storage::session_table::{SessionAction, SessionItem};
     use storage::websocket_table::WebsocketItem;
     use storage::IDynamoDbClient;
@@ -18,56 +21,143 @@ mod tests {
         test_setup::setup();
         let connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
+        let token = reconnect_token::generate();
         let request = SetSessionCommand {
-            connection_id,
-            session_id,
+            connection_id: ConnectionId::parse(&connection_id).unwrap(),
+            reconnect_token: ReconnectToken::parse(&token).unwrap(),
+            session_id: SessionId::parse(&session_id).unwrap(),
         };
         let result = handler(&request).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn errors_if_reconnect_token_is_wrong() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let old_connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &old_connection_id);
+        session.reconnect_token_hash = Some(reconnect_token::hash(&reconnect_token::generate()));
+
+        let connection_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new(&connection_id);
+        db.write(vec![connection.save()?, session.save()?]).await?;
+
+        let request = SetSessionCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            reconnect_token: ReconnectToken::parse(&reconnect_token::generate())?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        let result = handler(&request).await;
+        assert_eq!(result, Err(LogicError::NotAllowed));
+
+        // Doesn't bind the connection
+        let connection = WebsocketItem::from_db(&connection_id, &db).await?;
+        assert!(connection.session_id.is_none());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn updates_session() -> Result<(), LogicError> {
         test_setup::setup();
         let db = storage::get().await;
         let start_time = Utc::now();
 
+        let token = reconnect_token::generate();
         let old_connection_id = Uuid::new_v4().to_string();
         let session_id = Uuid::new_v4().to_string();
-        let session = SessionItem::new(&session_id, &old_connection_id);
+        let mut session = SessionItem::new(&session_id, &old_connection_id);
+        session.reconnect_token_hash = Some(reconnect_token::hash(&token));
+        let old_connection = WebsocketItem::new_with_session(&old_connection_id, &session_id);
 
         let connection_id = Uuid::new_v4().to_string();
         let connection = WebsocketItem::new(&connection_id);
 
-        db.write(vec![connection.save()?, session.save()?]).await?;
+        db.write(vec![
+            connection.save()?,
+            old_connection.save()?,
+            session.save()?,
+        ])
+        .await?;
 
         let request = SetSessionCommand {
-            connection_id: connection_id.clone(),
-            session_id: session_id.clone(),
+            connection_id: ConnectionId::parse(&connection_id)?,
+            reconnect_token: ReconnectToken::parse(&token)?,
+            session_id: SessionId::parse(&session_id)?,
         };
         let result = handler(&request).await;
 
         // Returns OK
         assert!(result.is_ok(), "Error: {:?}", result.err());
 
-        // Notifies the connection
+        // Notifies the connection with a new token
         let notifier = notifier::get().await;
         let messages = notifier.get_messages(&connection_id);
         assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("reconnectToken"));
 
         // Updates connection table
         let connection = WebsocketItem::from_db(&connection_id, &db).await?;
         assert!(connection.modified_at > start_time);
         let session_id = match connection.session_id {
             Some(session_id) => session_id,
-            None => return Err(LogicError::GetItemError("Session not found".to_string())),
+            None => return Err(LogicError::ItemNotFound("Session not found".to_string())),
         };
 
-        // Updates session table
+        // Unbinds the previous connection
+        let old_connection = WebsocketItem::from_db(&old_connection_id, &db).await?;
+        assert!(old_connection.session_id.is_none());
+
+        // Updates session table and rotates the token
         let session = SessionItem::from_db(&session_id, &db).await?;
         assert_eq!(session.connection_id, connection_id);
         assert_eq!(session.modified_action, SessionAction::Reconnected);
+        assert!(!reconnect_token::is_valid(&session, &token));
         assert!(connection.modified_at > start_time);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn tells_game_the_player_is_back() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let token = reconnect_token::generate();
+        let game_id = GameItem::create_game_code();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &Uuid::new_v4().to_string());
+        session.reconnect_token_hash = Some(reconnect_token::hash(&token));
+        session.game_id = Some(game_id.clone());
+        let mut player = PlayerItem::new(&session_id, &None, "Returning");
+        player.reconnect_deadline = Some(Utc::now() + chrono::TimeDelta::seconds(30));
+        let mut game = GameItem::new(&game_id, &session_id);
+        game.players.push(player);
+
+        let connection_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new(&connection_id);
+        db.write(vec![connection.save()?, session.save()?, game.save()?])
+            .await?;
+
+        let request = SetSessionCommand {
+            connection_id: ConnectionId::parse(&connection_id)?,
+            reconnect_token: ReconnectToken::parse(&token)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        handler(&request).await?;
+
+        let game = GameItem::from_db(&game_id, &db).await?;
+        assert_eq!(
+            game.players[0].connection_status(Utc::now()),
+            ConnectionStatus::Connected
+        );
+
+        // The new token, the game stream sends the game
+        let notifier = notifier::get().await;
+        let messages = notifier.get_messages(&connection_id);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("reconnectToken"));
+        Ok(())
+    }
 }
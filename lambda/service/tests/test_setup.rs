@@ -5,4 +5,8 @@ use std::env;
 pub fn setup() {
     env::set_var("WEBSOCKET_TABLE_NAME", "WEBSOCKET");
     env::set_var("GAME_TABLE_NAME", "GAME");
+    env::set_var("LEADERBOARD_TABLE_NAME", "LEADERBOARD");
+    env::set_var("PROFILE_TABLE_NAME", "PROFILE");
+    env::set_var("OUTBOX_TABLE_NAME", "OUTBOX");
+    env::set_var("RATE_LIMIT_TABLE_NAME", "RATE_LIMIT");
 }
@@ -0,0 +1,172 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use aws_sdk_dynamodb::types::{AttributeValue, TransactWriteItem};
+    use chrono::{TimeDelta, Utc};
+    use domain::errors::LogicError;
+    use serde_json::{json, Value};
+    use service::sweep::handler;
+    use storage::game_table::{GameItem, PlayerItem, RoundItem};
+    use storage::session_table::{SessionAction, SessionItem};
+    use storage::websocket_table::WebsocketItem;
+    use storage::IDynamoDbClient;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    // Each sweep takes everything expired, so tests sweep one at a time to
+    // see only their own items swept
+    static SWEEPING: Mutex<()> = Mutex::const_new(());
+
+    async fn sweep() -> Result<Value, LogicError> {
+        Ok(serde_json::from_str(&handler().await?).unwrap())
+    }
+
+    fn swept(connections: u64, sessions: u64, games: u64, rounds: u64) -> Value {
+        json!({
+            "connections": connections,
+            "sessions": sessions,
+            "games": games,
+            "rounds": rounds,
+            "failures": 0,
+        })
+    }
+
+    /// Saves the item as though its TTL ran out a minute ago.
+    fn expired(mut write: TransactWriteItem) -> TransactWriteItem {
+        let expires_at = (Utc::now() - TimeDelta::minutes(1)).timestamp();
+        write.put.as_mut().unwrap().item.insert(
+            "expires_at".to_string(),
+            AttributeValue::N(expires_at.to_string()),
+        );
+        write
+    }
+
+    #[tokio::test]
+    async fn disconnects_expired_connections() -> Result<(), LogicError> {
+        test_setup::setup();
+        let _sweeping = SWEEPING.lock().await;
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        let session = SessionItem::new(&session_id, &connection_id);
+        db.write(vec![expired(connection.save()?), session.save()?])
+            .await?;
+
+        assert_eq!(sweep().await?, swept(1, 0, 0, 0));
+
+        assert!(WebsocketItem::from_db(&connection_id, &db).await.is_err());
+        let session = SessionItem::from_db(&session_id, &db).await?;
+        assert_eq!(session.modified_action, SessionAction::PendingTimeout);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keeps_sessions_that_are_still_connected() -> Result<(), LogicError> {
+        test_setup::setup();
+        let _sweeping = SWEEPING.lock().await;
+        let db = storage::get().await;
+
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        let session = SessionItem::new(&session_id, &connection_id);
+        db.write(vec![connection.save()?, expired(session.save()?)])
+            .await?;
+
+        assert_eq!(sweep().await?, swept(0, 0, 0, 0));
+
+        assert!(SessionItem::from_db(&session_id, &db).await.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ends_abandoned_games() -> Result<(), LogicError> {
+        test_setup::setup();
+        let _sweeping = SWEEPING.lock().await;
+        let db = storage::get().await;
+
+        // One player's session is gone, the other's lingers
+        let game_id = GameItem::create_game_code();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &Uuid::new_v4().to_string());
+        session.game_id = Some(game_id.clone());
+        let mut game = GameItem::new(&game_id, &session_id);
+        game.players = vec![
+            PlayerItem::new(&Uuid::new_v4().to_string(), &None, "Gone"),
+            PlayerItem::new(&session_id, &None, "Lingering"),
+        ];
+        db.write(vec![expired(game.save()?), session.save()?])
+            .await?;
+
+        assert_eq!(sweep().await?, swept(0, 0, 1, 0));
+        assert!(GameItem::from_db(&game_id, &db).await.is_err());
+        assert!(SessionItem::from_db(&session_id, &db).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keeps_connected_players_sessions_when_ending_games() -> Result<(), LogicError> {
+        test_setup::setup();
+        let _sweeping = SWEEPING.lock().await;
+        let db = storage::get().await;
+
+        let game_id = GameItem::create_game_code();
+        let connection_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let connection = WebsocketItem::new_with_session(&connection_id, &session_id);
+        let mut session = SessionItem::new(&session_id, &connection_id);
+        session.game_id = Some(game_id.clone());
+        let mut game = GameItem::new(&game_id, &session_id);
+        game.players = vec![PlayerItem::new(&session_id, &None, "Connected")];
+        db.write(vec![
+            connection.save()?,
+            session.save()?,
+            expired(game.save()?),
+        ])
+        .await?;
+
+        assert_eq!(sweep().await?, swept(0, 0, 1, 0));
+
+        assert!(GameItem::from_db(&game_id, &db).await.is_err());
+        assert!(SessionItem::from_db(&session_id, &db).await.is_ok());
+        assert!(WebsocketItem::from_db(&connection_id, &db).await.is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn destroys_disconnected_expired_sessions() -> Result<(), LogicError> {
+        test_setup::setup();
+        let _sweeping = SWEEPING.lock().await;
+        let db = storage::get().await;
+
+        let session_id = Uuid::new_v4().to_string();
+        let session = SessionItem::new(&session_id, &Uuid::new_v4().to_string());
+        db.write_single(expired(session.save()?)).await?;
+
+        assert_eq!(sweep().await?, swept(0, 1, 0, 0));
+
+        assert!(SessionItem::from_db(&session_id, &db).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn deletes_expired_rounds() -> Result<(), LogicError> {
+        test_setup::setup();
+        let _sweeping = SWEEPING.lock().await;
+        let db = storage::get().await;
+
+        let mut game = GameItem::new(&GameItem::create_game_code(), "host");
+        let round = game.record_round();
+        db.write(vec![game.save()?, expired(round.save()?)]).await?;
+
+        assert_eq!(sweep().await?, swept(0, 0, 0, 1));
+        let rounds = RoundItem::from_db_batch(&game.instance_id, &[1], &db).await?;
+        assert!(rounds.is_empty());
+        assert!(GameItem::from_db(&game.game_id, &db).await.is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::commands::GetAccountCommand;
+    use domain::errors::LogicError;
+    use domain::values::AccountId;
+    use serde_json::Value;
+    use service::view_account::handler;
+    use storage::profile_table::ProfileItem;
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn returns_account_with_profile() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let mut profile = ProfileItem::new(&account_id);
+        profile.games_played = 2;
+        db.write_single(profile.save()?).await?;
+
+        let request = GetAccountCommand {
+            account_id: AccountId::parse(&account_id)?,
+            email: "player@example.com".to_string(),
+            username: "player".to_string(),
+        };
+        let result = handler(&request).await?;
+
+        let view: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(view["accountId"], account_id);
+        assert_eq!(view["email"], "player@example.com");
+        assert_eq!(view["profile"]["gamesPlayed"], 2);
+        Ok(())
+    }
+}
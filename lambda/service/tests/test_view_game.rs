@@ -0,0 +1,97 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::commands::{GetGameCommand, GetGameRoundsCommand};
+    use domain::errors::LogicError;
+    use domain::values::{AccountId, GameCode, Page};
+    use serde_json::Value;
+    use service::view_game::{handler, rounds};
+    use storage::game_table::{GameItem, PlayerItem};
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    async fn create_game(account_id: &str) -> Result<GameItem, LogicError> {
+        let db = storage::get().await;
+        let session_id = Uuid::new_v4().to_string();
+        let mut game = GameItem::new(&GameItem::create_game_code(), &session_id);
+        let player = PlayerItem::new(&session_id, &Some(account_id.to_string()), "nickname");
+        game.players.push(player);
+        db.write_single(game.save()?).await?;
+        Ok(game)
+    }
+
+    #[tokio::test]
+    async fn returns_game_state() -> Result<(), LogicError> {
+        test_setup::setup();
+        let account_id = Uuid::new_v4().to_string();
+        let game = create_game(&account_id).await?;
+
+        let request = GetGameCommand {
+            account_id: AccountId::parse(&account_id)?,
+            game_id: GameCode::parse(&game.game_id)?,
+        };
+        let result = handler(&request).await?;
+
+        let view: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(view["gameId"], game.game_id);
+        assert_eq!(view["players"][0]["nickname"], "nickname");
+        assert_eq!(view["roundFinished"], false);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_account_not_in_game() -> Result<(), LogicError> {
+        test_setup::setup();
+        let game = create_game(&Uuid::new_v4().to_string()).await?;
+
+        let request = GetGameCommand {
+            account_id: AccountId::parse(&Uuid::new_v4().to_string())?,
+            game_id: GameCode::parse(&game.game_id)?,
+        };
+        let result = handler(&request).await;
+        assert_eq!(result, Err(LogicError::NotAllowed));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_game_doesnt_exist() -> Result<(), LogicError> {
+        test_setup::setup();
+        let request = GetGameCommand {
+            account_id: AccountId::parse(&Uuid::new_v4().to_string())?,
+            game_id: GameCode::parse("0000")?,
+        };
+        let result = handler(&request).await;
+        assert!(matches!(result, Err(LogicError::ItemNotFound(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn returns_rounds_newest_first() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+        let account_id = Uuid::new_v4().to_string();
+        let mut game = create_game(&account_id).await?;
+        for _ in 0..3 {
+            let round = game.record_round();
+            game.version += 1;
+            db.write(vec![game.save()?, round.save()?]).await?;
+        }
+
+        let request = GetGameRoundsCommand {
+            account_id: AccountId::parse(&account_id)?,
+            game_id: GameCode::parse(&game.game_id)?,
+            page: Page::parse(None, Some(2))?,
+        };
+        let result = rounds(&request).await?;
+
+        let view: Value = serde_json::from_str(&result).unwrap();
+        let rounds = view["rounds"].as_array().unwrap();
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0]["roundId"], 3);
+        assert_eq!(rounds[1]["roundId"], 2);
+        assert_eq!(view["nextOffset"], 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+mod test_setup;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_setup;
+    use domain::commands::GetSessionDetailsCommand;
+    use domain::errors::LogicError;
+    use domain::values::{AccountId, SessionId};
+    use serde_json::Value;
+    use service::view_session::handler;
+    use storage::session_table::SessionItem;
+    use storage::IDynamoDbClient;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn returns_linked_session() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let account_id = Uuid::new_v4().to_string();
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &Uuid::new_v4().to_string());
+        session.account_id = Some(account_id.clone());
+        session.nickname = Some("nickname".to_string());
+        db.write_single(session.save()?).await?;
+
+        let request = GetSessionDetailsCommand {
+            account_id: AccountId::parse(&account_id)?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        let result = handler(&request).await?;
+
+        let view: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(view["sessionId"], session_id);
+        assert_eq!(view["nickname"], "nickname");
+        assert!(view["gameId"].is_null());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_if_session_belongs_to_another_account() -> Result<(), LogicError> {
+        test_setup::setup();
+        let db = storage::get().await;
+
+        let session_id = Uuid::new_v4().to_string();
+        let mut session = SessionItem::new(&session_id, &Uuid::new_v4().to_string());
+        session.account_id = Some(Uuid::new_v4().to_string());
+        db.write_single(session.save()?).await?;
+
+        let request = GetSessionDetailsCommand {
+            account_id: AccountId::parse(&Uuid::new_v4().to_string())?,
+            session_id: SessionId::parse(&session_id)?,
+        };
+        let result = handler(&request).await;
+        assert_eq!(result, Err(LogicError::NotAllowed));
+        Ok(())
+    }
+}
@@ -56,15 +56,23 @@ impl AttributeValueParser for i32 {
     }
 }
 
+impl AttributeValueParser for Option<i32> {
+    fn parse(value: Option<&AttributeValue>) -> Result<Self, LogicError> {
+        match value {
+            None => Ok(None),
+            Some(_) => i32::parse(value).map(Some),
+        }
+    }
+}
+
 impl AttributeValueParser for bool {
     fn parse(value: Option<&AttributeValue>) -> Result<Self, LogicError> {
         let value = value.ok_or(LogicError::DeserializationError(
             "Key not found".to_string(),
         ))?;
-        let result = value
+        let result = *value
             .as_bool()
-            .map_err(|_| LogicError::DeserializationError("Expected bool".to_string()))?
-            .clone();
+            .map_err(|_| LogicError::DeserializationError("Expected bool".to_string()))?;
         Ok(result)
     }
 }
@@ -85,3 +93,21 @@ impl AttributeValueParser for DateTime<Utc> {
         Ok(datetime)
     }
 }
+
+/// Expiry times are epoch seconds, the format DynamoDB's TTL reads.
+impl AttributeValueParser for Option<DateTime<Utc>> {
+    fn parse(value: Option<&AttributeValue>) -> Result<Self, LogicError> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let seconds = value
+            .as_n()
+            .map_err(|_| LogicError::DeserializationError("Expected number".to_string()))?
+            .parse::<i64>()
+            .map_err(|_| LogicError::DeserializationError("Could not parse number".to_string()))?;
+        let datetime = DateTime::from_timestamp(seconds, 0).ok_or(
+            LogicError::DeserializationError("Timestamp out of range".to_string()),
+        )?;
+        Ok(Some(datetime))
+    }
+}
@@ -7,7 +7,6 @@ use aws_sdk_dynamodb::{
 use aws_smithy_runtime_api::{client::result::SdkError, http::Response};
 use domain::errors::LogicError;
 use std::collections::HashMap;
-use std::env;
 
 pub struct Database {
     client: Client,
@@ -17,8 +16,8 @@ pub struct Database {
 #[cfg_attr(feature = "in_memory", allow(unused))]
 impl Database {
     pub async fn new() -> Self {
-        let region_name = env::var("AWS_REGION").unwrap_or_else(|_| "".to_string());
-        let table_name = env::var("TABLE_NAME").unwrap_or_else(|_| "".to_string());
+        let region_name = config::get().aws_region.clone();
+        let table_name = config::get().name_table_name.clone();
         let region_provider =
             RegionProviderChain::first_try(Region::new(region_name)).or_default_provider();
         let config = aws_config::defaults(BehaviorVersion::latest())
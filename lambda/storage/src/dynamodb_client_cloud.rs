@@ -1,11 +1,25 @@
-use crate::IDynamoDbClient;
+use crate::expiry::{ExpiryKind, EXPIRY_INDEX};
+use crate::{IDynamoDbClient, IndexQuery};
 use aws_config::meta::region::RegionProviderChain;
 use aws_config::{self, BehaviorVersion};
-use aws_sdk_dynamodb::types::{ItemResponse, TransactGetItem, TransactWriteItem};
+use aws_sdk_dynamodb::error::SdkError;
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::{
+    AttributeValue, Get, ItemResponse, KeysAndAttributes, TransactGetItem, TransactWriteItem,
+};
 use aws_sdk_dynamodb::{config::Region, Client};
+use chrono::{DateTime, Utc};
 use domain::errors::LogicError;
 use domain::utils;
-use std::env;
+use metrics::names::DYNAMODB_LATENCY;
+use std::collections::HashMap;
+use std::time::Duration;
+
+// DynamoDB's limit on a single BatchGetItem call
+const MAX_BATCH_GET_ITEMS: usize = 100;
+// Keys DynamoDB leaves unprocessed are retried with backoff this many times
+const MAX_BATCH_GET_ATTEMPTS: u32 = 5;
+const BATCH_GET_BACKOFF: Duration = Duration::from_millis(50);
 
 pub struct DynamoDbClient {
     client: Client,
@@ -14,7 +28,7 @@ pub struct DynamoDbClient {
 #[cfg_attr(feature = "in_memory", allow(unused))]
 impl DynamoDbClient {
     pub async fn new() -> Self {
-        let region_name = env::var("AWS_REGION").unwrap_or_else(|_| "".to_string());
+        let region_name = config::get().aws_region.clone();
         let region_provider =
             RegionProviderChain::first_try(Region::new(region_name)).or_default_provider();
         let config = aws_config::defaults(BehaviorVersion::latest())
@@ -24,10 +38,8 @@ impl DynamoDbClient {
         let client = Client::new(&config);
         DynamoDbClient { client }
     }
-}
 
-impl IDynamoDbClient for DynamoDbClient {
-    async fn read_single(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError> {
+    async fn transact_get(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError> {
         let result = self
             .client
             .transact_get_items()
@@ -42,7 +54,97 @@ impl IDynamoDbClient for DynamoDbClient {
         Ok(item)
     }
 
-    async fn write(&self, items: Vec<TransactWriteItem>) -> Result<(), LogicError> {
+    /// Reads the items without a transaction, so that one missing or
+    /// throttled item doesn't fail the rest. Responses are in request order.
+    async fn batch_get(
+        &self,
+        items: Vec<TransactGetItem>,
+    ) -> Result<Vec<ItemResponse>, LogicError> {
+        let gets = items
+            .into_iter()
+            .map(|item| {
+                item.get.ok_or(LogicError::GetItemError(
+                    "Only gets can be read".to_string(),
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // BatchGetItem rejects a request naming the same key twice
+        let mut unique: Vec<&Get> = vec![];
+        for get in &gets {
+            if !unique.iter().any(|other| Self::same_key(other, get)) {
+                unique.push(get);
+            }
+        }
+
+        let mut found = vec![];
+        for chunk in unique.chunks(MAX_BATCH_GET_ITEMS) {
+            let mut keys_by_table: HashMap<String, Vec<_>> = HashMap::new();
+            for get in chunk {
+                keys_by_table
+                    .entry(get.table_name.clone())
+                    .or_default()
+                    .push(get.key.clone());
+            }
+            let mut request_items = keys_by_table
+                .into_iter()
+                .map(|(table_name, keys)| {
+                    let keys_and_attributes = KeysAndAttributes::builder()
+                        .set_keys(Some(keys))
+                        .consistent_read(true)
+                        .build()
+                        .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+                    Ok((table_name, keys_and_attributes))
+                })
+                .collect::<Result<HashMap<_, _>, LogicError>>()?;
+
+            for attempt in 1.. {
+                let output = self
+                    .client
+                    .batch_get_item()
+                    .set_request_items(Some(request_items))
+                    .send()
+                    .await
+                    .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+                for (table_name, items) in output.responses.unwrap_or_default() {
+                    found.extend(items.into_iter().map(|item| (table_name.clone(), item)));
+                }
+                request_items = output.unprocessed_keys.unwrap_or_default();
+                if request_items.is_empty() {
+                    break;
+                }
+                if attempt == MAX_BATCH_GET_ATTEMPTS {
+                    return Err(LogicError::GetItemError(
+                        "Keys left unprocessed after retries".to_string(),
+                    ));
+                }
+                tokio::time::sleep(BATCH_GET_BACKOFF * 2u32.pow(attempt - 1)).await;
+            }
+        }
+
+        let responses = gets
+            .iter()
+            .map(|get| {
+                let item = found.iter().find(|(table_name, item)| {
+                    *table_name == get.table_name
+                        && get
+                            .key
+                            .iter()
+                            .all(|(name, value)| item.get(name) == Some(value))
+                });
+                ItemResponse::builder()
+                    .set_item(item.map(|(_, item)| item.clone()))
+                    .build()
+            })
+            .collect();
+        Ok(responses)
+    }
+
+    fn same_key(a: &Get, b: &Get) -> bool {
+        a.table_name == b.table_name && a.key == b.key
+    }
+
+    async fn transact_write(&self, items: Vec<TransactWriteItem>) -> Result<(), LogicError> {
         let result = self
             .client
             .transact_write_items()
@@ -51,11 +153,136 @@ impl IDynamoDbClient for DynamoDbClient {
             .await;
         match result {
             Ok(_) => Ok(()),
+            Err(e) if Self::is_condition_failure(&e) => {
+                Err(LogicError::ConditionalCheckFailed(e.to_string()))
+            }
             Err(e) => Err(LogicError::UpdateItemError(e.to_string())),
         }
     }
 
+    async fn scan(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        let mut items = vec![];
+        let mut start_key = None;
+        loop {
+            let output = self
+                .client
+                .scan()
+                .table_name(table_name)
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+            items.extend(output.items.unwrap_or_default());
+            start_key = output.last_evaluated_key;
+            if start_key.is_none() {
+                return Ok(items);
+            }
+        }
+    }
+
+    async fn query_expiry_index(
+        &self,
+        table_name: &str,
+        kind: ExpiryKind,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        let mut items = vec![];
+        let mut start_key = None;
+        loop {
+            let output = self
+                .client
+                .query()
+                .table_name(table_name)
+                .index_name(EXPIRY_INDEX)
+                .key_condition_expression("expiry_kind = :kind AND expires_at < :now")
+                .expression_attribute_values(":kind", AttributeValue::S(kind.as_str().to_string()))
+                .expression_attribute_values(":now", AttributeValue::N(now.timestamp().to_string()))
+                .set_exclusive_start_key(start_key)
+                .send()
+                .await
+                .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+            items.extend(output.items.unwrap_or_default());
+            start_key = output.last_evaluated_key;
+            if start_key.is_none() {
+                return Ok(items);
+            }
+        }
+    }
+
+    async fn query_index(
+        &self,
+        query: IndexQuery,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        let output = self
+            .client
+            .query()
+            .table_name(query.table_name)
+            .index_name(query.index_name)
+            .key_condition_expression("#key = :value")
+            .expression_attribute_names("#key", query.key)
+            .expression_attribute_values(":value", query.value)
+            .scan_index_forward(false)
+            .limit(query.limit)
+            .send()
+            .await
+            .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+        Ok(output.items.unwrap_or_default())
+    }
+
+    /// A transaction cancelled by a failed condition can be retried against
+    /// fresh items.
+    fn is_condition_failure(err: &SdkError<TransactWriteItemsError>) -> bool {
+        let Some(TransactWriteItemsError::TransactionCanceledException(e)) = err.as_service_error()
+        else {
+            return false;
+        };
+        e.cancellation_reasons()
+            .iter()
+            .any(|reason| reason.code() == Some("ConditionalCheckFailed"))
+    }
+}
+
+impl IDynamoDbClient for DynamoDbClient {
+    async fn read_single(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "read_single", self.transact_get(item)).await
+    }
+
+    async fn read(&self, items: Vec<TransactGetItem>) -> Result<Vec<ItemResponse>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "read", self.batch_get(items)).await
+    }
+
+    async fn write(&self, items: Vec<TransactWriteItem>) -> Result<(), LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "write", self.transact_write(items)).await
+    }
+
     async fn write_single(&self, item: TransactWriteItem) -> Result<(), LogicError> {
-        self.write(vec![item]).await
+        metrics::time(DYNAMODB_LATENCY, "write_single", self.transact_write(vec![item])).await
+    }
+
+    async fn query(
+        &self,
+        query: IndexQuery,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "query", self.query_index(query)).await
+    }
+
+    async fn query_expired(
+        &self,
+        table_name: &str,
+        kind: ExpiryKind,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        let query = self.query_expiry_index(table_name, kind, now);
+        metrics::time(DYNAMODB_LATENCY, "query_expired", query).await
+    }
+
+    async fn scan_all(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "scan_all", self.scan(table_name)).await
     }
 }
@@ -1,13 +1,21 @@
 use crate::attribute_value_parser::parse_attribute_value;
-use crate::IDynamoDbClient;
+use crate::expiry::{self, ExpiryKind};
+use crate::stream_record::StreamRecord;
+use crate::{IDynamoDbClient, IndexQuery};
 use aws_sdk_dynamodb::operation::transact_get_items::builders::TransactGetItemsOutputBuilder;
 use aws_sdk_dynamodb::types::{
     AttributeValue, Delete, ItemResponse, Put, TransactGetItem, TransactWriteItem,
 };
+use chrono::{DateTime, Utc};
 use domain::errors::LogicError;
 use domain::utils;
+use metrics::names::DYNAMODB_LATENCY;
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
+
+/// Called with the table name and every change written to it, standing in
+/// for the table streams a lambda reads in AWS.
+pub type OnChange = Box<dyn Fn(&str, StreamRecord) + Send + Sync>;
 
 pub struct FakeItem {
     pub hash_map: HashMap<String, AttributeValue>,
@@ -15,23 +23,59 @@ pub struct FakeItem {
 
 pub struct DynamoDbClient {
     game_table: RwLock<HashMap<String, FakeItem>>,
+    leaderboard_table: RwLock<HashMap<String, FakeItem>>,
+    outbox_table: RwLock<HashMap<String, FakeItem>>,
+    profile_table: RwLock<HashMap<String, FakeItem>>,
+    rate_limit_table: RwLock<HashMap<String, FakeItem>>,
     websocket_table: RwLock<HashMap<String, FakeItem>>,
+    on_change: RwLock<Option<OnChange>>,
+    // Held while a write checks and applies its items, so like a DynamoDB
+    // transaction it applies all of them or none
+    transaction: Mutex<()>,
 }
 
 #[cfg_attr(not(feature = "in_memory"), allow(unused))]
 impl DynamoDbClient {
     pub async fn new() -> Self {
         let game_table = RwLock::new(HashMap::new());
+        let leaderboard_table = RwLock::new(HashMap::new());
+        let outbox_table = RwLock::new(HashMap::new());
+        let profile_table = RwLock::new(HashMap::new());
+        let rate_limit_table = RwLock::new(HashMap::new());
         let websocket_table = RwLock::new(HashMap::new());
+        let on_change = RwLock::new(None);
+        let transaction = Mutex::new(());
         DynamoDbClient {
             game_table,
+            leaderboard_table,
+            outbox_table,
+            profile_table,
+            rate_limit_table,
             websocket_table,
+            on_change,
+            transaction,
+        }
+    }
+
+    pub fn stream_to(&self, on_change: OnChange) {
+        *self.on_change.write().unwrap() = Some(on_change);
+    }
+
+    // Called with the table still locked, so changes arrive in the order
+    // they were written
+    fn record_change(&self, table_name: &str, record: StreamRecord) {
+        if let Some(on_change) = self.on_change.read().unwrap().as_ref() {
+            on_change(table_name, record);
         }
     }
 
     fn get_table(&self, table_name: &str) -> &RwLock<HashMap<String, FakeItem>> {
         match table_name {
             "GAME" => &self.game_table,
+            "LEADERBOARD" => &self.leaderboard_table,
+            "OUTBOX" => &self.outbox_table,
+            "PROFILE" => &self.profile_table,
+            "RATE_LIMIT" => &self.rate_limit_table,
             "WEBSOCKET" => &self.websocket_table,
             _ => panic!("Unrecognised table {:?}", table_name),
         }
@@ -40,15 +84,29 @@ impl DynamoDbClient {
     fn get_primary_key(&self, table_name: &str) -> &str {
         match table_name {
             "GAME" => "id",
+            "LEADERBOARD" => "id",
+            "OUTBOX" => "id",
+            "PROFILE" => "account_id",
+            "RATE_LIMIT" => "id",
             "WEBSOCKET" => "connection_id",
             _ => panic!("Unrecognised table"),
         }
     }
 
+    /// The partition and sort key of each secondary index.
+    fn get_index_keys(&self, table_name: &str, index_name: &str) -> (&str, &str) {
+        match (table_name, index_name) {
+            ("LEADERBOARD", "board_score") => ("board_id", "score"),
+            _ => panic!("Unrecognised index {:?}", index_name),
+        }
+    }
+
     fn write_put(&self, put: Put) -> Result<(), LogicError> {
-        let table = self.get_table(&put.table_name);
+        let table_name = put.table_name.clone();
+        let table = self.get_table(&table_name);
         let primary_key_column = self.get_primary_key(&put.table_name);
         let primary_key = parse_attribute_value::<String>(put.item.get(primary_key_column))?;
+        let new_image = put.item.clone();
         let item = FakeItem {
             hash_map: put.item.clone(),
         };
@@ -57,12 +115,18 @@ impl DynamoDbClient {
             let existing_item = hash_map.get(&primary_key);
             self.check_put_condition(put, &existing_item)?;
         }
-        hash_map.insert(primary_key.to_string(), item);
+        let old_item = hash_map.insert(primary_key.to_string(), item);
+        let record = StreamRecord {
+            old_image: old_item.map(|item| item.hash_map),
+            new_image: Some(new_image),
+        };
+        self.record_change(&table_name, record);
         Ok(())
     }
 
     fn write_delete(&self, delete: Delete) -> Result<(), LogicError> {
-        let table = self.get_table(&delete.table_name);
+        let table_name = delete.table_name.clone();
+        let table = self.get_table(&table_name);
         let primary_key_column = self.get_primary_key(&delete.table_name);
         let primary_key = parse_attribute_value::<String>(delete.key.get(primary_key_column))?;
         let mut hash_map = table.write().unwrap();
@@ -70,7 +134,13 @@ impl DynamoDbClient {
             let existing_item = hash_map.get(&primary_key);
             self.check_delete_condition(delete, &existing_item)?;
         }
-        hash_map.remove(&primary_key.to_string());
+        if let Some(old_item) = hash_map.remove(&primary_key.to_string()) {
+            let record = StreamRecord {
+                old_image: Some(old_item.hash_map),
+                new_image: None,
+            };
+            self.record_change(&table_name, record);
+        }
         Ok(())
     }
 
@@ -85,23 +155,30 @@ impl DynamoDbClient {
                 "No condition".to_string(),
             ))?;
 
+        // Like DynamoDB, a failed condition is reported as such so callers
+        // can retry
         let must_be_new = expression.starts_with("attribute_not_exists");
         match (existing_item, must_be_new) {
-            (Some(_), true) => {
-                return Err(LogicError::UpdateItemError(
-                    "Item already exists".to_string(),
-                ));
+            (Some(existing_item), true) => {
+                let values = put.expression_attribute_values.as_ref();
+                if !self.is_lower_score(&expression, values, existing_item)? {
+                    return Err(LogicError::ConditionalCheckFailed(
+                        "Item already exists".to_string(),
+                    ));
+                }
             }
             (Some(existing_item), false) => {
                 let actual_version =
                     parse_attribute_value::<i32>(existing_item.hash_map.get("version"))?;
                 let new_version = parse_attribute_value::<i32>(put.item.get("version"))?;
                 if new_version != actual_version + 1 {
-                    return Err(LogicError::UpdateItemError("Version mismatch".to_string()));
+                    return Err(LogicError::ConditionalCheckFailed(
+                        "Version mismatch".to_string(),
+                    ));
                 }
             }
             (None, false) => {
-                return Err(LogicError::UpdateItemError(
+                return Err(LogicError::ConditionalCheckFailed(
                     "Item does not exist".to_string(),
                 ));
             }
@@ -110,6 +187,22 @@ impl DynamoDbClient {
         Ok(())
     }
 
+    /// Handles `attribute_not_exists(id) OR score < :score`, which only lets
+    /// a score be raised.
+    fn is_lower_score(
+        &self,
+        expression: &str,
+        values: Option<&HashMap<String, AttributeValue>>,
+        existing_item: &FakeItem,
+    ) -> Result<bool, LogicError> {
+        if !expression.contains("score < :score") {
+            return Ok(false);
+        }
+        let score = parse_attribute_value::<i32>(values.and_then(|values| values.get(":score")))?;
+        let existing_score = parse_attribute_value::<i32>(existing_item.hash_map.get("score"))?;
+        Ok(existing_score < score)
+    }
+
     fn check_delete_condition(
         &self,
         delete: Delete,
@@ -126,28 +219,28 @@ impl DynamoDbClient {
                 "No expression values".to_string(),
             ))?;
         let expected_version =
-            parse_attribute_value::<i32>(attributes.get(&":old_version".to_string()))?;
+            parse_attribute_value::<i32>(attributes.get(":old_version"))?;
 
         match existing_item {
             Some(existing_item) => {
                 let actual_version =
                     parse_attribute_value::<i32>(existing_item.hash_map.get("version"))?;
                 if expected_version != actual_version {
-                    return Err(LogicError::UpdateItemError("Version mismatch".to_string()));
+                    return Err(LogicError::ConditionalCheckFailed(
+                        "Version mismatch".to_string(),
+                    ));
                 }
             }
             None => {
-                return Err(LogicError::UpdateItemError(
+                return Err(LogicError::ConditionalCheckFailed(
                     "Item does not exist".to_string(),
                 ));
             }
         }
         Ok(())
     }
-}
 
-impl IDynamoDbClient for DynamoDbClient {
-    async fn read_single(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError> {
+    fn get_item(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError> {
         let get = item.get.ok_or(LogicError::GetItemError(
             "Only Gets are supported".to_string(),
         ))?;
@@ -155,13 +248,10 @@ impl IDynamoDbClient for DynamoDbClient {
         let primary_key_column = self.get_primary_key(&get.table_name);
         let hash_map = table.read().unwrap();
         let primary_key = parse_attribute_value::<String>(get.key.get(primary_key_column))?;
-        let item = hash_map
-            .get(&primary_key)
-            .ok_or(LogicError::GetItemError("Item not found".to_string()))?;
+        // Like DynamoDB, a missing item is an empty response rather than an error
+        let item = hash_map.get(&primary_key).map(|item| item.hash_map.clone());
 
-        let item_response = ItemResponse::builder()
-            .set_item(Some(item.hash_map.clone()))
-            .build();
+        let item_response = ItemResponse::builder().set_item(item).build();
         let output = TransactGetItemsOutputBuilder::default()
             .responses(item_response)
             .build();
@@ -172,14 +262,84 @@ impl IDynamoDbClient for DynamoDbClient {
         Ok(item)
     }
 
-    async fn write(&self, items: Vec<TransactWriteItem>) -> Result<(), LogicError> {
-        for item in items {
-            self.write_single(item).await?;
+    fn scan_table(&self, table_name: &str) -> Vec<HashMap<String, AttributeValue>> {
+        let table = self.get_table(table_name);
+        let hash_map = table.read().unwrap();
+        hash_map
+            .values()
+            .map(|item| item.hash_map.clone())
+            .collect()
+    }
+
+    /// Like the expiry index, only items saved with a kind are found.
+    fn query_expired_table(
+        &self,
+        table_name: &str,
+        kind: ExpiryKind,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        let kind = AttributeValue::S(kind.as_str().to_string());
+        let table = self.get_table(table_name);
+        let hash_map = table.read().unwrap();
+        let mut items = vec![];
+        for item in hash_map.values() {
+            if item.hash_map.get("expiry_kind") != Some(&kind) {
+                continue;
+            }
+            let expires_at =
+                parse_attribute_value::<Option<DateTime<Utc>>>(item.hash_map.get("expires_at"))?;
+            if expiry::is_expired(expires_at, now) {
+                items.push(item.hash_map.clone());
+            }
+        }
+        Ok(items)
+    }
+
+    fn query_table(
+        &self,
+        query: IndexQuery,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        let (key, sort_key) = self.get_index_keys(&query.table_name, &query.index_name);
+        let table = self.get_table(&query.table_name);
+        let hash_map = table.read().unwrap();
+        let mut items = vec![];
+        for item in hash_map.values() {
+            if item.hash_map.get(key) == Some(&query.value) {
+                let sort_value = parse_attribute_value::<i32>(item.hash_map.get(sort_key))?;
+                items.push((sort_value, item.hash_map.clone()));
+            }
+        }
+        items.sort_by_key(|(sort_value, _)| std::cmp::Reverse(*sort_value));
+        let limit = usize::try_from(query.limit).unwrap_or(0);
+        Ok(items
+            .into_iter()
+            .take(limit)
+            .map(|(_, item)| item)
+            .collect())
+    }
+
+    fn check_item(&self, item: TransactWriteItem) -> Result<(), LogicError> {
+        if let Some(put) = item.put {
+            let table = self.get_table(&put.table_name);
+            let primary_key_column = self.get_primary_key(&put.table_name);
+            let primary_key = parse_attribute_value::<String>(put.item.get(primary_key_column))?;
+            let hash_map = table.read().unwrap();
+            if put.condition_expression.is_some() {
+                self.check_put_condition(put, &hash_map.get(&primary_key))?;
+            }
+        } else if let Some(delete) = item.delete {
+            let table = self.get_table(&delete.table_name);
+            let primary_key_column = self.get_primary_key(&delete.table_name);
+            let primary_key = parse_attribute_value::<String>(delete.key.get(primary_key_column))?;
+            let hash_map = table.read().unwrap();
+            if delete.condition_expression.is_some() {
+                self.check_delete_condition(delete, &hash_map.get(&primary_key))?;
+            }
         }
         Ok(())
     }
 
-    async fn write_single(&self, item: TransactWriteItem) -> Result<(), LogicError> {
+    fn write_item(&self, item: TransactWriteItem) -> Result<(), LogicError> {
         if let Some(put) = item.put {
             self.write_put(put)?;
         } else if let Some(delete) = item.delete {
@@ -192,3 +352,67 @@ impl IDynamoDbClient for DynamoDbClient {
         Ok(())
     }
 }
+
+impl IDynamoDbClient for DynamoDbClient {
+    async fn read_single(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "read_single", async { self.get_item(item) }).await
+    }
+
+    async fn read(&self, items: Vec<TransactGetItem>) -> Result<Vec<ItemResponse>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "read", async {
+            items.into_iter().map(|item| self.get_item(item)).collect()
+        })
+        .await
+    }
+
+    async fn write(&self, items: Vec<TransactWriteItem>) -> Result<(), LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "write", async {
+            let _transaction = self.transaction.lock().unwrap();
+            for item in &items {
+                self.check_item(item.clone())?;
+            }
+            for item in items {
+                self.write_item(item)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    async fn write_single(&self, item: TransactWriteItem) -> Result<(), LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "write_single", async {
+            let _transaction = self.transaction.lock().unwrap();
+            self.write_item(item)
+        })
+        .await
+    }
+
+    async fn query(
+        &self,
+        query: IndexQuery,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "query", async { self.query_table(query) }).await
+    }
+
+    async fn query_expired(
+        &self,
+        table_name: &str,
+        kind: ExpiryKind,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "query_expired", async {
+            self.query_expired_table(table_name, kind, now)
+        })
+        .await
+    }
+
+    async fn scan_all(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError> {
+        metrics::time(DYNAMODB_LATENCY, "scan_all", async {
+            Ok(self.scan_table(table_name))
+        })
+        .await
+    }
+}
@@ -1,9 +1,42 @@
-use aws_sdk_dynamodb::types::{ItemResponse, TransactGetItem, TransactWriteItem};
+use crate::expiry::ExpiryKind;
+use aws_sdk_dynamodb::types::{AttributeValue, ItemResponse, TransactGetItem, TransactWriteItem};
+use chrono::{DateTime, Utc};
 use domain::errors::LogicError;
+use std::collections::HashMap;
+
+/// Items sharing one partition of a table's secondary index.
+pub struct IndexQuery {
+    pub table_name: String,
+    pub index_name: String,
+    pub key: String,
+    pub value: AttributeValue,
+    /// Reads at most this many, highest sort key first
+    pub limit: i32,
+}
 
 #[trait_variant::make(HttpService: Send)]
 pub trait IDynamoDbClient {
     async fn read_single(&self, item: TransactGetItem) -> Result<ItemResponse, LogicError>;
+    /// Reads the items in batches rather than a transaction, responding in the
+    /// same order.
+    async fn read(&self, items: Vec<TransactGetItem>) -> Result<Vec<ItemResponse>, LogicError>;
     async fn write(&self, items: Vec<TransactWriteItem>) -> Result<(), LogicError>;
     async fn write_single(&self, item: TransactWriteItem) -> Result<(), LogicError>;
+    async fn query(
+        &self,
+        query: IndexQuery,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError>;
+    /// Reads every item of the kind whose `expires_at` is before `now`, from
+    /// the table's expiry index.
+    async fn query_expired(
+        &self,
+        table_name: &str,
+        kind: ExpiryKind,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError>;
+    /// Reads every item in the table.
+    async fn scan_all(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>, LogicError>;
 }
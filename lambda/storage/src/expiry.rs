@@ -0,0 +1,40 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// The index the sweeper reads expired items from, keyed by `expiry_kind`
+/// and sorted by `expires_at`, so it never reads items that are still live.
+pub const EXPIRY_INDEX: &str = "expiry";
+
+/// What sort of item the sweeper is looking at, saved as `expiry_kind`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExpiryKind {
+    Connection,
+    Game,
+    Round,
+    Session,
+}
+
+impl ExpiryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpiryKind::Connection => "CONNECTION",
+            ExpiryKind::Game => "GAME",
+            ExpiryKind::Round => "ROUND",
+            ExpiryKind::Session => "SESSION",
+        }
+    }
+}
+
+/// Items hold an `expires_at` pushed back each time they're saved, so one
+/// that passes it has seen no activity for the TTL. The sweeper tears these
+/// down.
+pub fn expires_at(now: DateTime<Utc>, ttl_secs: u64) -> DateTime<Utc> {
+    i64::try_from(ttl_secs)
+        .ok()
+        .and_then(TimeDelta::try_seconds)
+        .and_then(|ttl| now.checked_add_signed(ttl))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+pub fn is_expired(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    expires_at.is_some_and(|expires_at| expires_at < now)
+}
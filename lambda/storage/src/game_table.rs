@@ -1,13 +1,14 @@
 use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::expiry::{self, ExpiryKind};
 use crate::{DynamoDbClient, IDynamoDbClient};
 use aws_sdk_dynamodb::types::{AttributeValue, Get, Put, TransactGetItem, TransactWriteItem};
 use chrono::{DateTime, Utc};
 use domain::errors::LogicError;
-use rand::distributions::Alphanumeric;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::{collections::HashMap, env};
+use std::collections::HashMap;
+use uuid::Uuid;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum GameAction {
@@ -18,6 +19,8 @@ pub enum GameAction {
     RollDice,
     StartSpectating,
     StopSpectating,
+    Disconnect,
+    Reconnect,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -73,6 +76,26 @@ pub enum RollResultNote {
     CockringHands,
 }
 
+impl RollResultNote {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RollResultNote::None => "",
+            RollResultNote::DualWield => "DUAL_WIELD",
+            RollResultNote::HeadOnTable => "HEAD_ON_TABLE",
+            RollResultNote::FinishDrink => "FINISH_DRINK",
+            RollResultNote::Pool => "POOL",
+            RollResultNote::SipDrink => "SIP_DRINK",
+            RollResultNote::Shower => "SHOWER",
+            RollResultNote::ThreeWayTie => "THREE_WAY_TIE",
+            RollResultNote::Tie => "TIE",
+            RollResultNote::UhOh => "UH_OH",
+            RollResultNote::Winner => "WINNER",
+            RollResultNote::WishPurchase => "WISH_PURCHASE",
+            RollResultNote::CockringHands => "COCKRING_HANDS",
+        }
+    }
+}
+
 impl GameAction {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -83,6 +106,8 @@ impl GameAction {
             GameAction::RollDice => "ROLL_DICE",
             GameAction::StartSpectating => "START_SPECTATING",
             GameAction::StopSpectating => "STOP_SPECTATING",
+            GameAction::Disconnect => "DISCONNECT",
+            GameAction::Reconnect => "RECONNECT",
         }
     }
 
@@ -95,6 +120,8 @@ impl GameAction {
             "ROLL_DICE" => Ok(GameAction::RollDice),
             "START_SPECTATING" => Ok(GameAction::StartSpectating),
             "STOP_SPECTATING" => Ok(GameAction::StopSpectating),
+            "DISCONNECT" => Ok(GameAction::Disconnect),
+            "RECONNECT" => Ok(GameAction::Reconnect),
             _ => Err(LogicError::DeserializationError(
                 "Invalid game action".to_string(),
             )),
@@ -142,6 +169,25 @@ impl RollResultItem {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConnectionStatus {
+    Connected,
+    /// Dropped, but the player can still come back.
+    Reconnecting,
+    /// Past the deadline, waiting to be removed from the game.
+    Disconnected,
+}
+
+impl ConnectionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectionStatus::Connected => "CONNECTED",
+            ConnectionStatus::Reconnecting => "RECONNECTING",
+            ConnectionStatus::Disconnected => "DISCONNECTED",
+        }
+    }
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct PlayerItem {
     pub player_id: String,
@@ -152,7 +198,15 @@ pub struct PlayerItem {
     pub outcome: RollResultNote,
     pub outcome_type: RollResultType,
     pub rolls: Vec<RollItem>,
-    // pub connection_status: ConnectionStatus,
+    /// Set while the player's connection is down. Their session times out,
+    /// removing them from the game, if they haven't reconnected by then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconnect_deadline: Option<DateTime<Utc>>,
+    /// Totals for this game, which its group leaderboards rank.
+    #[serde(default)]
+    pub rounds_won: i32,
+    #[serde(default)]
+    pub pools: i32,
 }
 
 impl PlayerItem {
@@ -166,6 +220,16 @@ impl PlayerItem {
             outcome: RollResultNote::None,
             outcome_type: RollResultType::None,
             finished: false,
+            reconnect_deadline: None,
+            rounds_won: 0,
+            pools: 0,
+        }
+    }
+    pub fn connection_status(&self, now: DateTime<Utc>) -> ConnectionStatus {
+        match self.reconnect_deadline {
+            None => ConnectionStatus::Connected,
+            Some(deadline) if deadline > now => ConnectionStatus::Reconnecting,
+            Some(_) => ConnectionStatus::Disconnected,
         }
     }
     pub fn vec_from_string(json_str: &str) -> Result<Vec<Self>, LogicError> {
@@ -176,16 +240,149 @@ impl PlayerItem {
     }
 }
 
+// Rounds can't be read once their game is gone, the sweeper deletes them
+// after this long
+const ROUND_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// A settled round, kept as its own item in the game table so the game item
+/// stays the same size however long it's played.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct RoundItem {
+    pub round_id: i32,
+    pub finished_at: DateTime<Utc>,
+    pub mr_eleven: Option<String>,
+    pub players: Vec<PlayerItem>,
+    #[serde(skip)]
+    pub instance_id: String,
+}
+
+impl RoundItem {
+    // Keyed by the game's instance, so a later game with the same code
+    // doesn't see these
+    fn id(instance_id: &str, round_id: i32) -> String {
+        format!("{instance_id}#round#{round_id}")
+    }
+
+    /// The game's rounds with these ids, skipping any that have expired.
+    pub async fn from_db_batch(
+        instance_id: &str,
+        round_ids: &[i32],
+        db: &DynamoDbClient,
+    ) -> Result<Vec<Self>, LogicError> {
+        if round_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let transactions = round_ids
+            .iter()
+            .map(|round_id| Self::get(instance_id, *round_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = db.read(transactions).await?;
+        outputs
+            .iter()
+            .filter_map(|output| output.item.as_ref())
+            .map(|item| Self::from_map(instance_id, item))
+            .collect()
+    }
+
+    /// Rounds past their TTL, whichever game they belong to.
+    pub async fn expired(now: DateTime<Utc>, db: &DynamoDbClient) -> Result<Vec<Self>, LogicError> {
+        let table_name = GameItem::get_table_name();
+        let items = db
+            .query_expired(&table_name, ExpiryKind::Round, now)
+            .await?;
+        let mut rounds = vec![];
+        for item in items {
+            let id = parse_attribute_value::<String>(item.get("id"))?;
+            let (instance_id, _) =
+                id.split_once("#round#")
+                    .ok_or(LogicError::DeserializationError(format!(
+                        "Not a round id {id}"
+                    )))?;
+            rounds.push(Self::from_map(instance_id, &item)?);
+        }
+        Ok(rounds)
+    }
+
+    pub fn from_map(
+        instance_id: &str,
+        hash_map: &HashMap<String, AttributeValue>,
+    ) -> Result<Self, LogicError> {
+        let round_str = parse_attribute_value::<String>(hash_map.get("round"))?;
+        let mut round: Self = serde_json::from_str(&round_str)
+            .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
+        round.instance_id = instance_id.to_string();
+        Ok(round)
+    }
+
+    pub fn get(instance_id: &str, round_id: i32) -> Result<TransactGetItem, LogicError> {
+        let get_item = Get::builder()
+            .table_name(GameItem::get_table_name())
+            .key("id", AttributeValue::S(Self::id(instance_id, round_id)))
+            .build()
+            .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+        let transaction_item = TransactGetItem::builder().get(get_item).build();
+        Ok(transaction_item)
+    }
+
+    pub fn delete(&self) -> Result<TransactWriteItem, LogicError> {
+        let delete_item = aws_sdk_dynamodb::types::Delete::builder()
+            .table_name(GameItem::get_table_name())
+            .key(
+                "id",
+                AttributeValue::S(Self::id(&self.instance_id, self.round_id)),
+            )
+            .build()
+            .map_err(|e| LogicError::DeleteItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().delete(delete_item).build();
+        Ok(transaction_item)
+    }
+
+    /// Saved alongside the game, whose version guards against a round being
+    /// recorded twice.
+    pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let round = serde_json::to_string(self)
+            .map_err(|e| LogicError::SerializationError(e.to_string()))?;
+        let expires_at = expiry::expires_at(self.finished_at, ROUND_TTL_SECS);
+        let put_item = Put::builder()
+            .table_name(GameItem::get_table_name())
+            .item(
+                "id",
+                AttributeValue::S(Self::id(&self.instance_id, self.round_id)),
+            )
+            .item("round", AttributeValue::S(round))
+            .item(
+                "expires_at",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            )
+            .item(
+                "expiry_kind",
+                AttributeValue::S(ExpiryKind::Round.as_str().to_string()),
+            )
+            .build()
+            .map_err(|e| LogicError::UpdateItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().put(put_item).build();
+        Ok(transaction_item)
+    }
+}
+
+/// Leaves out characters that are easily confused, like 0/O and 1/I.
+pub const GAME_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
 #[derive(Clone)]
 pub struct GameItem {
     pub game_id: String,
+    /// Tells this game apart from later ones given the same code.
+    pub instance_id: String,
+    /// When the game's code stops being reserved, pushed back on every save.
+    pub expires_at: Option<DateTime<Utc>>,
     pub modified_action: GameAction,
     pub modified_at: DateTime<Utc>,
     pub modified_by: String,
     pub mr_eleven: Option<String>,
     pub players: Vec<PlayerItem>,
     pub round_finished: bool,
-    // pub round_id: i32,
+    /// The id of the latest settled round, each stored as a `RoundItem`.
+    pub round_count: i32,
     // pub spectators: Vec<Spectator>,
     pub version: i32,
 }
@@ -194,12 +391,15 @@ impl GameItem {
     pub fn new(game_id: &str, session_id: &str) -> Self {
         GameItem {
             game_id: game_id.to_string(),
+            instance_id: Uuid::new_v4().to_string(),
+            expires_at: None,
             modified_action: GameAction::CreateGame,
             modified_at: Utc::now(),
             modified_by: session_id.to_string(),
             mr_eleven: None,
             players: Vec::new(),
             round_finished: false,
+            round_count: 0,
             version: 0,
         }
     }
@@ -209,13 +409,18 @@ impl GameItem {
         let output = db.read_single(transaction).await?;
         let attribute = output
             .item
-            .ok_or(LogicError::GetItemError("Item not found".to_string()))?;
+            .ok_or(LogicError::ItemNotFound("Item not found".to_string()))?;
         let item = Self::from_map(&attribute)?;
         Ok(item)
     }
 
     pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
         let game_id = parse_attribute_value::<String>(hash_map.get("id"))?;
+        // Games saved before instance ids keep their code's boards
+        let instance_id = parse_attribute_value::<Option<String>>(hash_map.get("instance_id"))?
+            .unwrap_or_else(|| game_id.clone());
+        let expires_at =
+            parse_attribute_value::<Option<DateTime<Utc>>>(hash_map.get("expires_at"))?;
         let modified_action = GameAction::from_str(&parse_attribute_value::<String>(
             hash_map.get("modified_action"),
         )?)?;
@@ -225,34 +430,63 @@ impl GameItem {
         let players_str = parse_attribute_value::<String>(hash_map.get("players"))?;
         let players = PlayerItem::vec_from_string(&players_str)?;
         let round_finished = parse_attribute_value::<bool>(hash_map.get("round_finished"))?;
+        let round_count = parse_attribute_value::<Option<i32>>(hash_map.get("round_count"))?;
         let version = parse_attribute_value::<i32>(hash_map.get("version"))?;
 
         let item = GameItem {
             game_id,
+            instance_id,
+            expires_at,
             modified_action,
             modified_at,
             modified_by,
             mr_eleven,
             players,
             round_finished,
+            round_count: round_count.unwrap_or(0),
             version,
         };
         Ok(item)
     }
 
+    /// Snapshots the settled round, to be saved alongside the game.
+    pub fn record_round(&mut self) -> RoundItem {
+        self.round_count += 1;
+        RoundItem {
+            round_id: self.round_count,
+            finished_at: Utc::now(),
+            mr_eleven: self.mr_eleven.clone(),
+            players: self.players.clone(),
+            instance_id: self.instance_id.clone(),
+        }
+    }
+
     fn get_table_name() -> String {
-        env::var("GAME_TABLE_NAME").unwrap_or_else(|_| "".to_string())
+        config::get().game_table_name.clone()
     }
 
     pub fn create_game_code() -> String {
         let mut rng = rand::thread_rng();
-        let game_code: String = (0..4)
-            .map(|_| rng.sample(Alphanumeric) as char)
-            .map(|c| c.to_ascii_uppercase())
+        let game_code: String = (0..config::get().game_code_length)
+            .map(|_| GAME_CODE_ALPHABET[rng.gen_range(0..GAME_CODE_ALPHABET.len())] as char)
             .collect();
         game_code
     }
 
+    /// Games not saved within the TTL, skipping the sessions that share their
+    /// table.
+    pub async fn expired(now: DateTime<Utc>, db: &DynamoDbClient) -> Result<Vec<Self>, LogicError> {
+        let table_name = Self::get_table_name();
+        let items = db.query_expired(&table_name, ExpiryKind::Game, now).await?;
+        items.iter().map(Self::from_map).collect()
+    }
+
+    /// Nobody has saved the game within the reservation, so it's abandoned
+    /// and due to be swept.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        expiry::is_expired(self.expires_at, now)
+    }
+
     pub fn get(game_id: &str) -> Result<TransactGetItem, LogicError> {
         let get_item = Get::builder()
             .table_name(Self::get_table_name())
@@ -263,10 +497,15 @@ impl GameItem {
         Ok(transaction_item)
     }
 
+    /// A new game only takes a free code, so an abandoned game keeps its
+    /// code until the sweeper has deleted it.
     pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let now = Utc::now();
+        let ttl = config::get().game_code_ttl_secs;
         let put_item = Put::builder()
             .table_name(Self::get_table_name())
             .item("id", AttributeValue::S(self.game_id.clone()))
+            .item("instance_id", AttributeValue::S(self.instance_id.clone()))
             .item(
                 "modified_action",
                 AttributeValue::S(self.modified_action.as_str().to_string()),
@@ -281,7 +520,19 @@ impl GameItem {
                 AttributeValue::S(PlayerItem::vec_to_string(&self.players)?),
             )
             .item("version", AttributeValue::N(self.version.to_string()))
-            .item("round_finished", AttributeValue::Bool(self.round_finished));
+            .item(
+                "expires_at",
+                AttributeValue::N(expiry::expires_at(now, ttl).timestamp().to_string()),
+            )
+            .item(
+                "expiry_kind",
+                AttributeValue::S(ExpiryKind::Game.as_str().to_string()),
+            )
+            .item("round_finished", AttributeValue::Bool(self.round_finished))
+            .item(
+                "round_count",
+                AttributeValue::N(self.round_count.to_string()),
+            );
 
         let put_item = match self.mr_eleven {
             Some(ref mr_eleven) => {
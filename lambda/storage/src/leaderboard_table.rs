@@ -0,0 +1,167 @@
+use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::{expiry, DynamoDbClient, IDynamoDbClient, IndexQuery};
+use aws_sdk_dynamodb::types::{AttributeValue, Get, Put, TransactGetItem, TransactWriteItem};
+use chrono::{DateTime, Utc};
+use domain::errors::LogicError;
+use domain::values::LeaderboardStat;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Boards only rank the top entries.
+pub const MAX_ENTRIES: usize = 100;
+
+// A group board can't be reached once its game is gone, DynamoDB drops it
+// after this long without a score
+const GROUP_ENTRY_TTL_SECS: u64 = 30 * 24 * 60 * 60;
+
+const BOARD_INDEX: &str = "board_score";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LeaderboardScope {
+    Global,
+    /// The game's instance id, so a new game reusing the code starts afresh.
+    Group(String),
+}
+
+/// One account's score on one board. Each is its own item, so rounds
+/// finishing at the same time never contend over a whole board.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardItem {
+    pub board_id: String,
+    pub account_id: String,
+    pub nickname: String,
+    pub score: i32,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub modified_at: DateTime<Utc>,
+}
+
+impl LeaderboardItem {
+    pub fn new(
+        scope: &LeaderboardScope,
+        stat: LeaderboardStat,
+        account_id: &str,
+        nickname: &str,
+        score: i32,
+    ) -> Self {
+        let now = Utc::now();
+        let expires_at = match scope {
+            LeaderboardScope::Global => None,
+            LeaderboardScope::Group(_) => Some(expiry::expires_at(now, GROUP_ENTRY_TTL_SECS)),
+        };
+        LeaderboardItem {
+            board_id: Self::board_id(scope, stat),
+            account_id: account_id.to_string(),
+            nickname: nickname.to_string(),
+            score,
+            expires_at,
+            modified_at: now,
+        }
+    }
+
+    pub fn board_id(scope: &LeaderboardScope, stat: LeaderboardStat) -> String {
+        match scope {
+            LeaderboardScope::Global => format!("GLOBAL#{}", stat.as_str()),
+            LeaderboardScope::Group(instance_id) => {
+                format!("GROUP#{}#{}", instance_id, stat.as_str())
+            }
+        }
+    }
+
+    fn id(board_id: &str, account_id: &str) -> String {
+        format!("{board_id}#{account_id}")
+    }
+
+    pub async fn from_db(
+        board_id: &str,
+        account_id: &str,
+        db: &DynamoDbClient,
+    ) -> Result<Self, LogicError> {
+        let transaction = Self::get(board_id, account_id)?;
+        let output = db.read_single(transaction).await?;
+        let attribute = output
+            .item
+            .ok_or(LogicError::ItemNotFound("Item not found".to_string()))?;
+        let item = Self::from_map(&attribute)?;
+        Ok(item)
+    }
+
+    /// The board's highest scores, best first. Boards are created by the
+    /// first score, so a missing board is empty.
+    pub async fn top(board_id: &str, db: &DynamoDbClient) -> Result<Vec<Self>, LogicError> {
+        let query = IndexQuery {
+            table_name: Self::get_table_name(),
+            index_name: BOARD_INDEX.to_string(),
+            key: "board_id".to_string(),
+            value: AttributeValue::S(board_id.to_string()),
+            limit: MAX_ENTRIES as i32,
+        };
+        let items = db.query(query).await?;
+        items.iter().map(Self::from_map).collect()
+    }
+
+    pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
+        let board_id = parse_attribute_value::<String>(hash_map.get("board_id"))?;
+        let account_id = parse_attribute_value::<String>(hash_map.get("account_id"))?;
+        let nickname = parse_attribute_value::<String>(hash_map.get("nickname"))?;
+        let score = parse_attribute_value::<i32>(hash_map.get("score"))?;
+        let expires_at =
+            parse_attribute_value::<Option<DateTime<Utc>>>(hash_map.get("expires_at"))?;
+        let modified_at = parse_attribute_value::<DateTime<Utc>>(hash_map.get("modified_at"))?;
+        let item = LeaderboardItem {
+            board_id,
+            account_id,
+            nickname,
+            score,
+            expires_at,
+            modified_at,
+        };
+        Ok(item)
+    }
+
+    fn get_table_name() -> String {
+        config::get().leaderboard_table_name.clone()
+    }
+
+    pub fn get(board_id: &str, account_id: &str) -> Result<TransactGetItem, LogicError> {
+        let get_item = Get::builder()
+            .table_name(Self::get_table_name())
+            .key("id", AttributeValue::S(Self::id(board_id, account_id)))
+            .build()
+            .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+        let transaction_item = TransactGetItem::builder().get(get_item).build();
+        Ok(transaction_item)
+    }
+
+    /// Only ever raises the score, so the same entry can be saved again, and
+    /// one from an earlier round arriving late is a failed condition.
+    pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let put_item = Put::builder()
+            .table_name(Self::get_table_name())
+            .item(
+                "id",
+                AttributeValue::S(Self::id(&self.board_id, &self.account_id)),
+            )
+            .item("board_id", AttributeValue::S(self.board_id.clone()))
+            .item("account_id", AttributeValue::S(self.account_id.clone()))
+            .item("nickname", AttributeValue::S(self.nickname.clone()))
+            .item("score", AttributeValue::N(self.score.to_string()))
+            .item(
+                "modified_at",
+                AttributeValue::S(self.modified_at.format(DATETIME_FORMAT).to_string()),
+            );
+        let put_item = match self.expires_at {
+            Some(expires_at) => put_item.item(
+                "expires_at",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            ),
+            None => put_item,
+        };
+        let put_item = put_item
+            .condition_expression("attribute_not_exists(id) OR score < :score")
+            .expression_attribute_values(":score", AttributeValue::N(self.score.to_string()))
+            .build()
+            .map_err(|e| LogicError::UpdateItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().put(put_item).build();
+        Ok(transaction_item)
+    }
+}
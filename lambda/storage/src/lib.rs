@@ -7,8 +7,14 @@ mod dynamodb_client_cloud;
 mod dynamodb_client_instance;
 mod dynamodb_client_local;
 mod dynamodb_client_trait;
+pub mod expiry;
 pub mod game_table;
+pub mod leaderboard_table;
+pub mod outbox_table;
+pub mod profile_table;
+pub mod rate_limit_table;
 pub mod session_table;
+pub mod stream_record;
 pub mod websocket_table;
 
 #[cfg(not(feature = "in_memory"))]
@@ -23,4 +29,4 @@ pub use dynamodb_client_cloud::DynamoDbClient;
 pub use dynamodb_client_instance::get;
 #[cfg(feature = "in_memory")]
 pub use dynamodb_client_local::DynamoDbClient;
-pub use dynamodb_client_trait::IDynamoDbClient;
+pub use dynamodb_client_trait::{IDynamoDbClient, IndexQuery};
@@ -0,0 +1,136 @@
+use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::leaderboard_table::LeaderboardItem;
+use crate::{expiry, DynamoDbClient, IDynamoDbClient};
+use aws_sdk_dynamodb::types::{
+    AttributeValue, Delete, Get, Put, TransactGetItem, TransactWriteItem,
+};
+use chrono::{DateTime, Utc};
+use domain::commands::ScheduledCommand;
+use domain::errors::LogicError;
+use domain::events::DomainEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// Long enough to ride out a relay outage, after which DynamoDB drops them
+const OUTBOX_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Something to tell the outside world, or a write shared with other games,
+/// to carry out once the state change it was written with has committed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum OutboxEntry {
+    Event {
+        event: DomainEvent,
+    },
+    Notification {
+        connection_id: String,
+        data: String,
+    },
+    Leaderboard {
+        entries: Vec<LeaderboardItem>,
+    },
+    Schedule {
+        command: ScheduledCommand,
+        run_at: DateTime<Utc>,
+    },
+}
+
+/// An outbox entry, written in the same transaction as the state change and
+/// deleted by the relay once delivered.
+#[derive(Clone, Debug)]
+pub struct OutboxItem {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub entry: OutboxEntry,
+}
+
+impl OutboxItem {
+    pub fn new(entry: OutboxEntry) -> Self {
+        OutboxItem {
+            id: Uuid::new_v4().to_string(),
+            created_at: Utc::now(),
+            entry,
+        }
+    }
+
+    pub async fn from_db(id: &str, db: &DynamoDbClient) -> Result<Self, LogicError> {
+        let output = db.read_single(Self::get(id)?).await?;
+        let attribute = output
+            .item
+            .ok_or(LogicError::ItemNotFound("Item not found".to_string()))?;
+        Self::from_map(&attribute)
+    }
+
+    pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
+        let id = parse_attribute_value::<String>(hash_map.get("id"))?;
+        let created_at = parse_attribute_value::<DateTime<Utc>>(hash_map.get("created_at"))?;
+        let entry = parse_attribute_value::<String>(hash_map.get("entry"))?;
+        let entry = serde_json::from_str(&entry)
+            .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
+        let item = OutboxItem {
+            id,
+            created_at,
+            entry,
+        };
+        Ok(item)
+    }
+
+    /// Every entry not yet delivered, oldest first.
+    pub async fn pending(db: &DynamoDbClient) -> Result<Vec<Self>, LogicError> {
+        let items = db.scan_all(&Self::get_table_name()).await?;
+        let mut items = items
+            .iter()
+            .map(Self::from_map)
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by_key(|item| item.created_at);
+        Ok(items)
+    }
+
+    fn get_table_name() -> String {
+        config::get().outbox_table_name.clone()
+    }
+
+    pub fn get(id: &str) -> Result<TransactGetItem, LogicError> {
+        let get_item = Get::builder()
+            .table_name(Self::get_table_name())
+            .key("id", AttributeValue::S(id.to_string()))
+            .build()
+            .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+        let transaction_item = TransactGetItem::builder().get(get_item).build();
+        Ok(transaction_item)
+    }
+
+    pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let entry = serde_json::to_string(&self.entry)
+            .map_err(|e| LogicError::SerializationError(e.to_string()))?;
+        let expires_at = expiry::expires_at(self.created_at, OUTBOX_TTL_SECS);
+        let put_item = Put::builder()
+            .table_name(Self::get_table_name())
+            .item("id", AttributeValue::S(self.id.clone()))
+            .item(
+                "created_at",
+                AttributeValue::S(self.created_at.format(DATETIME_FORMAT).to_string()),
+            )
+            .item("entry", AttributeValue::S(entry))
+            .item(
+                "expires_at",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            )
+            .condition_expression("attribute_not_exists(id)")
+            .build()
+            .map_err(|e| LogicError::UpdateItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().put(put_item).build();
+        Ok(transaction_item)
+    }
+
+    pub fn delete(&self) -> Result<TransactWriteItem, LogicError> {
+        let delete_item = Delete::builder()
+            .table_name(Self::get_table_name())
+            .key("id", AttributeValue::S(self.id.clone()))
+            .build()
+            .map_err(|e| LogicError::DeleteItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().delete(delete_item).build();
+        Ok(transaction_item)
+    }
+}
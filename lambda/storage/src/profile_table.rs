@@ -0,0 +1,146 @@
+use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::{DynamoDbClient, IDynamoDbClient};
+use aws_sdk_dynamodb::types::{AttributeValue, Get, Put, TransactGetItem, TransactWriteItem};
+use chrono::{DateTime, Utc};
+use domain::errors::LogicError;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileItem {
+    pub account_id: String,
+    pub games_played: i32,
+    pub longest_win_streak: i32,
+    pub modified_at: DateTime<Utc>,
+    pub outcome_counts: BTreeMap<String, i32>,
+    pub rounds_played: i32,
+    pub rounds_won: i32,
+    pub times_mr_eleven: i32,
+    pub version: i32,
+}
+
+impl ProfileItem {
+    pub fn new(account_id: &str) -> Self {
+        ProfileItem {
+            account_id: account_id.to_string(),
+            games_played: 0,
+            longest_win_streak: 0,
+            modified_at: Utc::now(),
+            outcome_counts: BTreeMap::new(),
+            rounds_played: 0,
+            rounds_won: 0,
+            times_mr_eleven: 0,
+            version: 0,
+        }
+    }
+
+    pub async fn from_db(account_id: &str, db: &DynamoDbClient) -> Result<Self, LogicError> {
+        let transaction = Self::get(account_id)?;
+        let output = db.read_single(transaction).await?;
+        let attribute = output
+            .item
+            .ok_or(LogicError::ItemNotFound("Item not found".to_string()))?;
+        let item = Self::from_map(&attribute)?;
+        Ok(item)
+    }
+
+    /// Profiles are created the first time a stat is recorded, so a missing
+    /// item is an empty profile rather than an error.
+    pub async fn from_db_or_new(account_id: &str, db: &DynamoDbClient) -> Result<Self, LogicError> {
+        let transaction = Self::get(account_id)?;
+        let output = db.read_single(transaction).await?;
+        match output.item {
+            Some(attribute) => Self::from_map(&attribute),
+            None => Ok(Self::new(account_id)),
+        }
+    }
+
+    pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
+        let account_id = parse_attribute_value::<String>(hash_map.get("account_id"))?;
+        let games_played = parse_attribute_value::<i32>(hash_map.get("games_played"))?;
+        let longest_win_streak = parse_attribute_value::<i32>(hash_map.get("longest_win_streak"))?;
+        let modified_at = parse_attribute_value::<DateTime<Utc>>(hash_map.get("modified_at"))?;
+        let outcome_counts_str = parse_attribute_value::<String>(hash_map.get("outcome_counts"))?;
+        let outcome_counts = serde_json::from_str(&outcome_counts_str)
+            .map_err(|e| LogicError::DeserializationError(e.to_string()))?;
+        let rounds_played = parse_attribute_value::<i32>(hash_map.get("rounds_played"))?;
+        let rounds_won = parse_attribute_value::<i32>(hash_map.get("rounds_won"))?;
+        let times_mr_eleven = parse_attribute_value::<i32>(hash_map.get("times_mr_eleven"))?;
+        let version = parse_attribute_value::<i32>(hash_map.get("version"))?;
+
+        let item = ProfileItem {
+            account_id,
+            games_played,
+            longest_win_streak,
+            modified_at,
+            outcome_counts,
+            rounds_played,
+            rounds_won,
+            times_mr_eleven,
+            version,
+        };
+        Ok(item)
+    }
+
+    fn get_table_name() -> String {
+        config::get().profile_table_name.clone()
+    }
+
+    pub fn get(account_id: &str) -> Result<TransactGetItem, LogicError> {
+        let get_item = Get::builder()
+            .table_name(Self::get_table_name())
+            .key("account_id", AttributeValue::S(account_id.to_string()))
+            .build()
+            .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+        let transaction_item = TransactGetItem::builder().get(get_item).build();
+        Ok(transaction_item)
+    }
+
+    pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let outcome_counts = serde_json::to_string(&self.outcome_counts)
+            .map_err(|e| LogicError::SerializationError(e.to_string()))?;
+        let put_item = Put::builder()
+            .table_name(Self::get_table_name())
+            .item("account_id", AttributeValue::S(self.account_id.clone()))
+            .item(
+                "games_played",
+                AttributeValue::N(self.games_played.to_string()),
+            )
+            .item(
+                "longest_win_streak",
+                AttributeValue::N(self.longest_win_streak.to_string()),
+            )
+            .item(
+                "modified_at",
+                AttributeValue::S(self.modified_at.format(DATETIME_FORMAT).to_string()),
+            )
+            .item("outcome_counts", AttributeValue::S(outcome_counts))
+            .item(
+                "rounds_played",
+                AttributeValue::N(self.rounds_played.to_string()),
+            )
+            .item("rounds_won", AttributeValue::N(self.rounds_won.to_string()))
+            .item(
+                "times_mr_eleven",
+                AttributeValue::N(self.times_mr_eleven.to_string()),
+            )
+            .item("version", AttributeValue::N(self.version.to_string()));
+
+        let old_version = self.version - 1;
+        let put_item = if old_version < 0 {
+            put_item.condition_expression("attribute_not_exists(account_id)")
+        } else {
+            put_item
+                .condition_expression("version = :old_version")
+                .expression_attribute_values(
+                    ":old_version",
+                    AttributeValue::N(old_version.to_string()),
+                )
+        };
+        let put_item = put_item
+            .build()
+            .map_err(|e| LogicError::UpdateItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().put(put_item).build();
+        Ok(transaction_item)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::{DynamoDbClient, IDynamoDbClient};
+use aws_sdk_dynamodb::types::{AttributeValue, Get, Put, TransactGetItem, TransactWriteItem};
+use chrono::{DateTime, Utc};
+use domain::errors::LogicError;
+use std::collections::HashMap;
+
+/// A token bucket, with whole tokens added as each refill interval passes
+/// since `refilled_at`. Once full again it's no different from a new bucket,
+/// so DynamoDB can drop it after `expires_at`.
+#[derive(Clone, Debug)]
+pub struct RateLimitItem {
+    pub bucket_id: String,
+    pub refilled_at: DateTime<Utc>,
+    pub tokens: i32,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub version: i32,
+}
+
+impl RateLimitItem {
+    pub fn new(bucket_id: &str, tokens: i32) -> Self {
+        RateLimitItem {
+            bucket_id: bucket_id.to_string(),
+            refilled_at: Utc::now(),
+            tokens,
+            expires_at: None,
+            version: 0,
+        }
+    }
+
+    /// Buckets start full, so a missing item is a new bucket.
+    pub async fn from_db_or_new(
+        bucket_id: &str,
+        tokens: i32,
+        db: &DynamoDbClient,
+    ) -> Result<Self, LogicError> {
+        let transaction = Self::get(bucket_id)?;
+        let output = db.read_single(transaction).await?;
+        match output.item {
+            Some(attribute) => {
+                let mut item = Self::from_map(&attribute)?;
+                item.version += 1;
+                Ok(item)
+            }
+            None => Ok(Self::new(bucket_id, tokens)),
+        }
+    }
+
+    pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
+        let bucket_id = parse_attribute_value::<String>(hash_map.get("id"))?;
+        let refilled_at = parse_attribute_value::<DateTime<Utc>>(hash_map.get("refilled_at"))?;
+        let tokens = parse_attribute_value::<i32>(hash_map.get("tokens"))?;
+        let expires_at =
+            parse_attribute_value::<Option<DateTime<Utc>>>(hash_map.get("expires_at"))?;
+        let version = parse_attribute_value::<i32>(hash_map.get("version"))?;
+        let item = RateLimitItem {
+            bucket_id,
+            refilled_at,
+            tokens,
+            expires_at,
+            version,
+        };
+        Ok(item)
+    }
+
+    fn get_table_name() -> String {
+        config::get().rate_limit_table_name.clone()
+    }
+
+    pub fn get(bucket_id: &str) -> Result<TransactGetItem, LogicError> {
+        let get_item = Get::builder()
+            .table_name(Self::get_table_name())
+            .key("id", AttributeValue::S(bucket_id.to_string()))
+            .build()
+            .map_err(|e| LogicError::GetItemError(e.to_string()))?;
+        let transaction_item = TransactGetItem::builder().get(get_item).build();
+        Ok(transaction_item)
+    }
+
+    pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let put_item = Put::builder()
+            .table_name(Self::get_table_name())
+            .item("id", AttributeValue::S(self.bucket_id.clone()))
+            .item(
+                "refilled_at",
+                AttributeValue::S(self.refilled_at.format(DATETIME_FORMAT).to_string()),
+            )
+            .item("tokens", AttributeValue::N(self.tokens.to_string()))
+            .item("version", AttributeValue::N(self.version.to_string()));
+        let put_item = match self.expires_at {
+            Some(expires_at) => put_item.item(
+                "expires_at",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            ),
+            None => put_item,
+        };
+
+        let old_version = self.version - 1;
+        let put_item = if old_version < 0 {
+            put_item.condition_expression("attribute_not_exists(id)")
+        } else {
+            put_item
+                .condition_expression("version = :old_version")
+                .expression_attribute_values(
+                    ":old_version",
+                    AttributeValue::N(old_version.to_string()),
+                )
+        };
+        let put_item = put_item
+            .build()
+            .map_err(|e| LogicError::UpdateItemError(e.to_string()))?;
+        let transaction_item = TransactWriteItem::builder().put(put_item).build();
+        Ok(transaction_item)
+    }
+}
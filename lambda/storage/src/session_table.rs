@@ -1,12 +1,14 @@
 use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::expiry::{self, ExpiryKind};
 use crate::{DynamoDbClient, IDynamoDbClient};
 use aws_sdk_dynamodb::types::{AttributeValue, Get, Put, TransactGetItem, TransactWriteItem};
 use chrono::{DateTime, Utc};
 use domain::errors::LogicError;
-use std::{collections::HashMap, env};
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum SessionAction {
+    Authenticated,
     CreateConnection,
     SetNickname,
     JoinGame,
@@ -16,6 +18,7 @@ pub enum SessionAction {
 impl SessionAction {
     pub fn as_str(&self) -> &'static str {
         match self {
+            SessionAction::Authenticated => "AUTHENTICATED",
             SessionAction::CreateConnection => "CREATE_CONNECTION",
             SessionAction::SetNickname => "SET_NICKNAME",
             SessionAction::JoinGame => "JOIN_GAME",
@@ -26,6 +29,7 @@ impl SessionAction {
 
     fn from_str(s: &str) -> Result<Self, LogicError> {
         match s {
+            "AUTHENTICATED" => Ok(SessionAction::Authenticated),
             "CREATE_CONNECTION" => Ok(SessionAction::CreateConnection),
             "SET_NICKNAME" => Ok(SessionAction::SetNickname),
             "JOIN_GAME" => Ok(SessionAction::JoinGame),
@@ -42,10 +46,13 @@ impl SessionAction {
 pub struct SessionItem {
     pub account_id: Option<String>,
     pub connection_id: String,
+    /// Pushed back on every save, see `expiry`.
+    pub expires_at: Option<DateTime<Utc>>,
     pub game_id: Option<String>,
     pub modified_at: DateTime<Utc>,
     pub modified_action: SessionAction,
     pub nickname: Option<String>,
+    pub reconnect_token_hash: Option<String>,
     pub session_id: String,
     pub version: i32,
 }
@@ -55,10 +62,12 @@ impl SessionItem {
         SessionItem {
             account_id: None,
             connection_id: connection_id.to_string(),
+            expires_at: None,
             game_id: None,
             nickname: None,
             modified_at: Utc::now(),
             modified_action: SessionAction::CreateConnection,
+            reconnect_token_hash: None,
             session_id: session_id.to_string(),
             version: 0,
         }
@@ -69,38 +78,82 @@ impl SessionItem {
         let output = db.read_single(transaction).await?;
         let attribute = output
             .item
-            .ok_or(LogicError::GetItemError("Item not found".to_string()))?;
+            .ok_or(LogicError::ItemNotFound("Item not found".to_string()))?;
         let item = Self::from_map(&attribute)?;
         Ok(item)
     }
 
+    /// Reads the sessions together. One that's missing or unreadable is an
+    /// error for that session alone.
+    pub async fn from_db_batch(
+        session_ids: &[String],
+        db: &DynamoDbClient,
+    ) -> Result<Vec<Result<Self, LogicError>>, LogicError> {
+        if session_ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let transactions = session_ids
+            .iter()
+            .map(|session_id| Self::get(session_id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = db.read(transactions).await?;
+        let sessions = outputs
+            .iter()
+            .map(|output| match &output.item {
+                Some(item) => Self::from_map(item),
+                None => Err(LogicError::ItemNotFound("Session not found".to_string())),
+            })
+            .collect();
+        Ok(sessions)
+    }
+
     pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
         let account_id = parse_attribute_value::<Option<String>>(hash_map.get("account_id"))?;
         let connection_id = parse_attribute_value::<String>(hash_map.get("connection_id"))?;
+        let expires_at =
+            parse_attribute_value::<Option<DateTime<Utc>>>(hash_map.get("expires_at"))?;
         let game_id = parse_attribute_value::<Option<String>>(hash_map.get("game_id"))?;
         let modified_at = parse_attribute_value::<DateTime<Utc>>(hash_map.get("modified_at"))?;
         let modified_action = SessionAction::from_str(&parse_attribute_value::<String>(
             hash_map.get("modified_action"),
         )?)?;
         let nickname = parse_attribute_value::<Option<String>>(hash_map.get("nickname"))?;
+        let reconnect_token_hash =
+            parse_attribute_value::<Option<String>>(hash_map.get("reconnect_token_hash"))?;
         let session_id = parse_attribute_value::<String>(hash_map.get("id"))?;
         let version = parse_attribute_value::<i32>(hash_map.get("version"))?;
 
         let item = SessionItem {
             account_id,
             connection_id,
+            expires_at,
             game_id,
             modified_at,
             modified_action,
             nickname,
+            reconnect_token_hash,
             session_id,
             version,
         };
         Ok(item)
     }
 
+    /// Sessions not saved within the TTL, skipping the games that share
+    /// their table.
+    pub async fn expired(now: DateTime<Utc>, db: &DynamoDbClient) -> Result<Vec<Self>, LogicError> {
+        let table_name = Self::get_table_name();
+        let items = db
+            .query_expired(&table_name, ExpiryKind::Session, now)
+            .await?;
+        items.iter().map(Self::from_map).collect()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        expiry::is_expired(self.expires_at, now)
+    }
+
     fn get_table_name() -> String {
-        env::var("GAME_TABLE_NAME").unwrap_or_else(|_| "".to_string())
+        config::get().game_table_name.clone()
     }
 
     pub fn get(session_id: &str) -> Result<TransactGetItem, LogicError> {
@@ -114,9 +167,18 @@ impl SessionItem {
     }
 
     pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let expires_at = expiry::expires_at(Utc::now(), config::get().session_ttl_secs);
         let put_item = Put::builder()
             .table_name(Self::get_table_name())
             .item("id", AttributeValue::S(self.session_id.clone()))
+            .item(
+                "expires_at",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            )
+            .item(
+                "expiry_kind",
+                AttributeValue::S(ExpiryKind::Session.as_str().to_string()),
+            )
             .item(
                 "connection_id",
                 AttributeValue::S(self.connection_id.to_string()),
@@ -150,6 +212,13 @@ impl SessionItem {
             None => put_item,
         };
 
+        let put_item = match self.reconnect_token_hash {
+            Some(ref hash) => {
+                put_item.item("reconnect_token_hash", AttributeValue::S(hash.to_string()))
+            }
+            None => put_item,
+        };
+
         let old_version = self.version - 1;
         let put_item = if old_version < 0 {
             put_item.condition_expression("attribute_not_exists(id)")
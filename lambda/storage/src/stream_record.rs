@@ -0,0 +1,10 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use std::collections::HashMap;
+
+/// A change to one item, as a table's stream reports it. An image is None
+/// when the item didn't exist before or after the change.
+#[derive(Clone, Debug, Default)]
+pub struct StreamRecord {
+    pub old_image: Option<HashMap<String, AttributeValue>>,
+    pub new_image: Option<HashMap<String, AttributeValue>>,
+}
@@ -1,13 +1,16 @@
 use crate::attribute_value_parser::{parse_attribute_value, DATETIME_FORMAT};
+use crate::expiry::{self, ExpiryKind};
 use crate::{DynamoDbClient, IDynamoDbClient};
 use aws_sdk_dynamodb::types::{AttributeValue, Get, Put, TransactGetItem, TransactWriteItem};
 use chrono::{DateTime, Utc};
 use domain::errors::LogicError;
-use std::{collections::HashMap, env};
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct WebsocketItem {
     pub connection_id: String,
+    /// Pushed back on every save, see `expiry`.
+    pub expires_at: Option<DateTime<Utc>>,
     pub session_id: Option<String>,
     pub version: i32,
     pub modified_at: DateTime<Utc>,
@@ -17,6 +20,7 @@ impl WebsocketItem {
     pub fn new(connection_id: &str) -> Self {
         WebsocketItem {
             connection_id: connection_id.to_string(),
+            expires_at: None,
             session_id: None,
             version: 0,
             modified_at: Utc::now(),
@@ -26,6 +30,7 @@ impl WebsocketItem {
     pub fn new_with_session(connection_id: &str, session_id: &str) -> Self {
         WebsocketItem {
             connection_id: connection_id.to_string(),
+            expires_at: None,
             session_id: Some(session_id.to_string()),
             version: 0,
             modified_at: Utc::now(),
@@ -37,18 +42,21 @@ impl WebsocketItem {
         let output = db.read_single(transaction).await?;
         let attribute = output
             .item
-            .ok_or(LogicError::GetItemError("Item not found".to_string()))?;
+            .ok_or(LogicError::ItemNotFound("Item not found".to_string()))?;
         let item = Self::from_map(&attribute)?;
         Ok(item)
     }
 
     pub fn from_map(hash_map: &HashMap<String, AttributeValue>) -> Result<Self, LogicError> {
         let connection_id = parse_attribute_value::<String>(hash_map.get("connection_id"))?;
+        let expires_at =
+            parse_attribute_value::<Option<DateTime<Utc>>>(hash_map.get("expires_at"))?;
         let session_id = parse_attribute_value::<Option<String>>(hash_map.get("session_id"))?;
         let version = parse_attribute_value::<i32>(hash_map.get("version"))?;
         let modified_at = parse_attribute_value::<DateTime<Utc>>(hash_map.get("modified_at"))?;
         let item = WebsocketItem {
             connection_id,
+            expires_at,
             session_id,
             version,
             modified_at,
@@ -56,8 +64,20 @@ impl WebsocketItem {
         Ok(item)
     }
 
+    pub async fn expired(now: DateTime<Utc>, db: &DynamoDbClient) -> Result<Vec<Self>, LogicError> {
+        let table_name = Self::get_table_name();
+        let items = db
+            .query_expired(&table_name, ExpiryKind::Connection, now)
+            .await?;
+        items.iter().map(Self::from_map).collect()
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        expiry::is_expired(self.expires_at, now)
+    }
+
     fn get_table_name() -> String {
-        env::var("WEBSOCKET_TABLE_NAME").unwrap_or_else(|_| "".to_string())
+        config::get().websocket_table_name.clone()
     }
 
     pub fn get(connection_id: &str) -> Result<TransactGetItem, LogicError> {
@@ -74,12 +94,21 @@ impl WebsocketItem {
     }
 
     pub fn save(&self) -> Result<TransactWriteItem, LogicError> {
+        let expires_at = expiry::expires_at(Utc::now(), config::get().connection_ttl_secs);
         let put_item = Put::builder()
             .table_name(Self::get_table_name())
             .item(
                 "connection_id",
                 AttributeValue::S(self.connection_id.to_string()),
             )
+            .item(
+                "expires_at",
+                AttributeValue::N(expires_at.timestamp().to_string()),
+            )
+            .item(
+                "expiry_kind",
+                AttributeValue::S(ExpiryKind::Connection.as_str().to_string()),
+            )
             .item("version", AttributeValue::N(self.version.to_string()))
             .item(
                 "modified_at",
@@ -6,12 +6,33 @@ locals {
   all_integrations = [
     aws_api_gateway_method.hello,
     aws_api_gateway_method.goodbye,
+    aws_api_gateway_method.account,
+    aws_api_gateway_method.game,
+    aws_api_gateway_method.game_rounds,
+    aws_api_gateway_method.session,
+    aws_api_gateway_method.leaderboard,
+    aws_api_gateway_method.profile,
+    aws_api_gateway_method.health,
 
     aws_api_gateway_integration.hello,
     aws_api_gateway_integration.goodbye,
+    aws_api_gateway_integration.account,
+    aws_api_gateway_integration.game,
+    aws_api_gateway_integration.game_rounds,
+    aws_api_gateway_integration.session,
+    aws_api_gateway_integration.leaderboard,
+    aws_api_gateway_integration.profile,
+    aws_api_gateway_integration.health,
 
     aws_api_gateway_method_response.hello_200,
     aws_api_gateway_method_response.goodbye_200,
+    aws_api_gateway_method_response.account_200,
+    aws_api_gateway_method_response.game_200,
+    aws_api_gateway_method_response.game_rounds_200,
+    aws_api_gateway_method_response.session_200,
+    aws_api_gateway_method_response.leaderboard_200,
+    aws_api_gateway_method_response.profile_200,
+    aws_api_gateway_method_response.health_200,
   ]
 }
 
@@ -92,3 +113,290 @@ resource "aws_api_gateway_method_response" "goodbye_200" {
     "method.response.header.Access-Control-Allow-Origin"  = true
   }
 }
+
+# profile
+
+
+resource "aws_api_gateway_resource" "profile" {
+  path_part   = "profile"
+  parent_id   = aws_api_gateway_rest_api.gateway.root_resource_id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "profile" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.profile.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "profile" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.profile.id
+  http_method             = aws_api_gateway_method.profile.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "profile_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.profile.id
+  http_method = aws_api_gateway_integration.profile.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
+
+# health
+
+resource "aws_api_gateway_resource" "health" {
+  path_part   = "health"
+  parent_id   = aws_api_gateway_rest_api.gateway.root_resource_id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "health" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.health.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "health" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.health.id
+  http_method             = aws_api_gateway_method.health.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "health_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.health.id
+  http_method = aws_api_gateway_integration.health.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
+
+# leaderboard
+
+
+resource "aws_api_gateway_resource" "leaderboard" {
+  path_part   = "leaderboard"
+  parent_id   = aws_api_gateway_rest_api.gateway.root_resource_id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "leaderboard" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.leaderboard.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "leaderboard" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.leaderboard.id
+  http_method             = aws_api_gateway_method.leaderboard.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "leaderboard_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.leaderboard.id
+  http_method = aws_api_gateway_integration.leaderboard.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
+
+# games
+
+
+resource "aws_api_gateway_resource" "games" {
+  path_part   = "games"
+  parent_id   = aws_api_gateway_rest_api.gateway.root_resource_id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_resource" "game" {
+  path_part   = "{game_id}"
+  parent_id   = aws_api_gateway_resource.games.id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "game" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.game.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "game" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.game.id
+  http_method             = aws_api_gateway_method.game.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "game_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.game.id
+  http_method = aws_api_gateway_integration.game.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
+
+resource "aws_api_gateway_resource" "game_rounds" {
+  path_part   = "rounds"
+  parent_id   = aws_api_gateway_resource.game.id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "game_rounds" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.game_rounds.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "game_rounds" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.game_rounds.id
+  http_method             = aws_api_gateway_method.game_rounds.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "game_rounds_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.game_rounds.id
+  http_method = aws_api_gateway_integration.game_rounds.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
+
+# sessions
+
+
+resource "aws_api_gateway_resource" "sessions" {
+  path_part   = "sessions"
+  parent_id   = aws_api_gateway_rest_api.gateway.root_resource_id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_resource" "session" {
+  path_part   = "{session_id}"
+  parent_id   = aws_api_gateway_resource.sessions.id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "session" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.session.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "session" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.session.id
+  http_method             = aws_api_gateway_method.session.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "session_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.session.id
+  http_method = aws_api_gateway_integration.session.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
+
+# accounts
+
+
+resource "aws_api_gateway_resource" "accounts" {
+  path_part   = "accounts"
+  parent_id   = aws_api_gateway_rest_api.gateway.root_resource_id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_resource" "account" {
+  path_part   = "me"
+  parent_id   = aws_api_gateway_resource.accounts.id
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+}
+
+resource "aws_api_gateway_method" "account" {
+  rest_api_id   = aws_api_gateway_rest_api.gateway.id
+  resource_id   = aws_api_gateway_resource.account.id
+  http_method   = "GET"
+  authorization = "COGNITO_USER_POOLS"
+  authorizer_id = aws_api_gateway_authorizer.gateway.id
+}
+
+resource "aws_api_gateway_integration" "account" {
+  rest_api_id             = aws_api_gateway_rest_api.gateway.id
+  resource_id             = aws_api_gateway_resource.account.id
+  http_method             = aws_api_gateway_method.account.http_method
+  uri                     = aws_lambda_function.http.invoke_arn
+  content_handling        = "CONVERT_TO_TEXT"
+  integration_http_method = "POST"
+  type                    = "AWS_PROXY"
+}
+
+resource "aws_api_gateway_method_response" "account_200" {
+  rest_api_id = aws_api_gateway_rest_api.gateway.id
+  resource_id = aws_api_gateway_resource.account.id
+  http_method = aws_api_gateway_integration.account.http_method
+  status_code = "200"
+  response_parameters = {
+    "method.response.header.Access-Control-Allow-Headers" = true
+    "method.response.header.Access-Control-Allow-Methods" = true
+    "method.response.header.Access-Control-Allow-Origin"  = true
+  }
+}
@@ -3,12 +3,103 @@ resource "aws_dynamodb_table" "game" {
   name         = "${local.prefix}Game"
   hash_key     = "id"
   billing_mode = "PAY_PER_REQUEST"
-  # stream_enabled   = true
-  # stream_view_type = "NEW_AND_OLD_IMAGES"
+  # Players are sent the game from the stream once a change commits
+  stream_enabled   = true
+  stream_view_type = "NEW_AND_OLD_IMAGES"
   attribute {
     name = "id"
     type = "S"
   }
+  attribute {
+    name = "expiry_kind"
+    type = "S"
+  }
+  attribute {
+    name = "expires_at"
+    type = "N"
+  }
+  # The sweeper reads expired items from here rather than scanning
+  global_secondary_index {
+    name            = "expiry"
+    hash_key        = "expiry_kind"
+    range_key       = "expires_at"
+    projection_type = "ALL"
+  }
+  # No TTL, a game deleted behind the sweeper's back would never tell its
+  # players. The sweeper deletes expired rounds too.
+}
+
+# One item per account on each board, ranked by the board_score index
+resource "aws_dynamodb_table" "leaderboard" {
+  name         = "${local.prefix}Leaderboard"
+  hash_key     = "id"
+  billing_mode = "PAY_PER_REQUEST"
+  attribute {
+    name = "id"
+    type = "S"
+  }
+  attribute {
+    name = "board_id"
+    type = "S"
+  }
+  attribute {
+    name = "score"
+    type = "N"
+  }
+  global_secondary_index {
+    name            = "board_score"
+    hash_key        = "board_id"
+    range_key       = "score"
+    projection_type = "ALL"
+  }
+  # Group boards outlive their game by a while
+  ttl {
+    attribute_name = "expires_at"
+    enabled        = true
+  }
+}
+
+# Events and notifications written alongside the state change, the stream
+# hands each one to the outbox lambda
+resource "aws_dynamodb_table" "outbox" {
+  name             = "${local.prefix}Outbox"
+  hash_key         = "id"
+  billing_mode     = "PAY_PER_REQUEST"
+  stream_enabled   = true
+  stream_view_type = "NEW_IMAGE"
+  attribute {
+    name = "id"
+    type = "S"
+  }
+  ttl {
+    attribute_name = "expires_at"
+    enabled        = true
+  }
+}
+
+resource "aws_dynamodb_table" "profile" {
+  name         = "${local.prefix}Profile"
+  hash_key     = "account_id"
+  billing_mode = "PAY_PER_REQUEST"
+  attribute {
+    name = "account_id"
+    type = "S"
+  }
+}
+
+resource "aws_dynamodb_table" "rate_limit" {
+  name         = "${local.prefix}RateLimit"
+  hash_key     = "id"
+  billing_mode = "PAY_PER_REQUEST"
+  attribute {
+    name = "id"
+    type = "S"
+  }
+  # Buckets that have filled up again
+  ttl {
+    attribute_name = "expires_at"
+    enabled        = true
+  }
 }
 
 resource "aws_dynamodb_table" "websocket_connection" {
@@ -19,6 +110,26 @@ resource "aws_dynamodb_table" "websocket_connection" {
     name = "connection_id"
     type = "S"
   }
+  attribute {
+    name = "expiry_kind"
+    type = "S"
+  }
+  attribute {
+    name = "expires_at"
+    type = "N"
+  }
+  # The sweeper reads expired items from here rather than scanning
+  global_secondary_index {
+    name            = "expiry"
+    hash_key        = "expiry_kind"
+    range_key       = "expires_at"
+    projection_type = "ALL"
+  }
+  # A backstop, a session whose connection has gone is still swept
+  ttl {
+    attribute_name = "expires_at"
+    enabled        = true
+  }
 }
 
 resource "aws_dynamodb_table" "database" {
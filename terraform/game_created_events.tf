@@ -7,8 +7,8 @@ resource "aws_cloudwatch_event_rule" "game_created" {
   description   = "A game has been created"
   event_pattern = <<-EOF
     {
-      "source": ["${local.prefix}.GameCreated"],
-      "detail-type": ["Game created"]
+      "source": ["${local.prefix}.Game"],
+      "detail-type": ["GameCreated"]
     }
   EOF
 }
@@ -87,7 +87,7 @@ data "aws_iam_policy_document" "admin_email" {
     ]
     principals {
       type        = "Service"
-      identifiers = ["events.amazonaws.com"]
+      identifiers = ["cloudwatch.amazonaws.com", "events.amazonaws.com"]
     }
     resources = [
       aws_sns_topic.admin_email.arn,
@@ -0,0 +1,23 @@
+# Send players the game each time a change to it commits. Sessions and rounds
+# share the table, only items with players reach the lambda, and deleted games
+# have nobody left to tell
+
+resource "aws_lambda_event_source_mapping" "broadcast_game" {
+  event_source_arn        = aws_dynamodb_table.game.stream_arn
+  function_name           = aws_lambda_function.game_stream.function_name
+  starting_position       = "LATEST"
+  batch_size              = 10
+  maximum_retry_attempts  = 3
+  function_response_types = ["ReportBatchItemFailures"]
+  filter_criteria {
+    filter {
+      pattern = jsonencode({
+        dynamodb = {
+          NewImage = {
+            players = { S = [{ exists = true }] }
+          }
+        }
+      })
+    }
+  }
+}
@@ -14,11 +14,19 @@ resource "aws_lambda_function" "http" {
   ]
   environment {
     variables = {
-      DATABASE             = aws_dynamodb_table.database.name,
-      WEBSOCKET_TABLE_NAME = aws_dynamodb_table.websocket_connection.name,
-      GAME_TABLE_NAME      = aws_dynamodb_table.game.name,
-      REGION_NAME          = local.region,
-      API_GATEWAY_URL      = aws_apigatewayv2_stage.websocket.invoke_url,
+      NAME_TABLE_NAME        = aws_dynamodb_table.database.name,
+      WEBSOCKET_TABLE_NAME   = aws_dynamodb_table.websocket_connection.name,
+      GAME_TABLE_NAME        = aws_dynamodb_table.game.name,
+      LEADERBOARD_TABLE_NAME = aws_dynamodb_table.leaderboard.name,
+      OUTBOX_TABLE_NAME      = aws_dynamodb_table.outbox.name,
+      PROFILE_TABLE_NAME     = aws_dynamodb_table.profile.name,
+      RATE_LIMIT_TABLE_NAME  = aws_dynamodb_table.rate_limit.name,
+      REGION_NAME            = local.region,
+      API_GATEWAY_URL        = aws_apigatewayv2_stage.websocket.invoke_url,
+      EVENT_SOURCE           = local.prefix,
+      METRICS_NAMESPACE      = local.prefix,
+      COGNITO_USER_POOL_ID   = aws_cognito_user_pool.users.id,
+      COGNITO_CLIENT_ID      = aws_cognito_user_pool_client.users.id,
     }
   }
 }
@@ -28,7 +36,7 @@ resource "aws_lambda_function" "sqs" {
   image_uri     = "${aws_ecr_repository.lambda.repository_url}@${data.aws_ecr_image.lambda.id}"
   function_name = "${local.prefix}-SQS"
   role          = aws_iam_role.lambda.arn
-  timeout       = 5
+  timeout       = 15
   image_config {
     entry_point = ["/api_sqs_entry"]
   }
@@ -38,11 +46,115 @@ resource "aws_lambda_function" "sqs" {
   ]
   environment {
     variables = {
-      DATABASE             = aws_dynamodb_table.database.name,
-      WEBSOCKET_TABLE_NAME = aws_dynamodb_table.websocket_connection.name,
-      GAME_TABLE_NAME      = aws_dynamodb_table.game.name,
-      REGION_NAME          = local.region,
-      API_GATEWAY_URL      = aws_apigatewayv2_stage.websocket.invoke_url,
+      NAME_TABLE_NAME        = aws_dynamodb_table.database.name,
+      WEBSOCKET_TABLE_NAME   = aws_dynamodb_table.websocket_connection.name,
+      GAME_TABLE_NAME        = aws_dynamodb_table.game.name,
+      LEADERBOARD_TABLE_NAME = aws_dynamodb_table.leaderboard.name,
+      OUTBOX_TABLE_NAME      = aws_dynamodb_table.outbox.name,
+      PROFILE_TABLE_NAME     = aws_dynamodb_table.profile.name,
+      RATE_LIMIT_TABLE_NAME  = aws_dynamodb_table.rate_limit.name,
+      REGION_NAME            = local.region,
+      API_GATEWAY_URL        = aws_apigatewayv2_stage.websocket.invoke_url,
+      EVENT_SOURCE           = local.prefix,
+      METRICS_NAMESPACE      = local.prefix,
+      COGNITO_USER_POOL_ID   = aws_cognito_user_pool.users.id,
+      COGNITO_CLIENT_ID      = aws_cognito_user_pool_client.users.id,
+    }
+  }
+}
+
+resource "aws_lambda_function" "sweep" {
+  package_type  = "Image"
+  image_uri     = "${aws_ecr_repository.lambda.repository_url}@${data.aws_ecr_image.lambda.id}"
+  function_name = "${local.prefix}-Sweep"
+  role          = aws_iam_role.lambda.arn
+  timeout       = 60
+  image_config {
+    entry_point = ["/api_sweep_entry"]
+  }
+  depends_on = [
+    aws_cloudwatch_log_group.sweep,
+    terraform_data.lambda_push,
+  ]
+  environment {
+    variables = {
+      NAME_TABLE_NAME        = aws_dynamodb_table.database.name,
+      WEBSOCKET_TABLE_NAME   = aws_dynamodb_table.websocket_connection.name,
+      GAME_TABLE_NAME        = aws_dynamodb_table.game.name,
+      LEADERBOARD_TABLE_NAME = aws_dynamodb_table.leaderboard.name,
+      OUTBOX_TABLE_NAME      = aws_dynamodb_table.outbox.name,
+      PROFILE_TABLE_NAME     = aws_dynamodb_table.profile.name,
+      RATE_LIMIT_TABLE_NAME  = aws_dynamodb_table.rate_limit.name,
+      REGION_NAME            = local.region,
+      API_GATEWAY_URL        = aws_apigatewayv2_stage.websocket.invoke_url,
+      EVENT_SOURCE           = local.prefix,
+      METRICS_NAMESPACE      = local.prefix,
+      COGNITO_USER_POOL_ID   = aws_cognito_user_pool.users.id,
+      COGNITO_CLIENT_ID      = aws_cognito_user_pool_client.users.id,
+    }
+  }
+}
+
+resource "aws_lambda_function" "outbox" {
+  package_type  = "Image"
+  image_uri     = "${aws_ecr_repository.lambda.repository_url}@${data.aws_ecr_image.lambda.id}"
+  function_name = "${local.prefix}-Outbox"
+  role          = aws_iam_role.lambda.arn
+  timeout       = 15
+  image_config {
+    entry_point = ["/api_outbox_entry"]
+  }
+  depends_on = [
+    aws_cloudwatch_log_group.outbox,
+    terraform_data.lambda_push,
+  ]
+  environment {
+    variables = {
+      NAME_TABLE_NAME        = aws_dynamodb_table.database.name,
+      WEBSOCKET_TABLE_NAME   = aws_dynamodb_table.websocket_connection.name,
+      GAME_TABLE_NAME        = aws_dynamodb_table.game.name,
+      LEADERBOARD_TABLE_NAME = aws_dynamodb_table.leaderboard.name,
+      OUTBOX_TABLE_NAME      = aws_dynamodb_table.outbox.name,
+      PROFILE_TABLE_NAME     = aws_dynamodb_table.profile.name,
+      RATE_LIMIT_TABLE_NAME  = aws_dynamodb_table.rate_limit.name,
+      REGION_NAME            = local.region,
+      API_GATEWAY_URL        = aws_apigatewayv2_stage.websocket.invoke_url,
+      EVENT_SOURCE           = local.prefix,
+      METRICS_NAMESPACE      = local.prefix,
+      COGNITO_USER_POOL_ID   = aws_cognito_user_pool.users.id,
+      COGNITO_CLIENT_ID      = aws_cognito_user_pool_client.users.id,
+    }
+  }
+}
+
+resource "aws_lambda_function" "game_stream" {
+  package_type  = "Image"
+  image_uri     = "${aws_ecr_repository.lambda.repository_url}@${data.aws_ecr_image.lambda.id}"
+  function_name = "${local.prefix}-GameStream"
+  role          = aws_iam_role.lambda.arn
+  timeout       = 15
+  image_config {
+    entry_point = ["/api_game_stream_entry"]
+  }
+  depends_on = [
+    aws_cloudwatch_log_group.game_stream,
+    terraform_data.lambda_push,
+  ]
+  environment {
+    variables = {
+      NAME_TABLE_NAME        = aws_dynamodb_table.database.name,
+      WEBSOCKET_TABLE_NAME   = aws_dynamodb_table.websocket_connection.name,
+      GAME_TABLE_NAME        = aws_dynamodb_table.game.name,
+      LEADERBOARD_TABLE_NAME = aws_dynamodb_table.leaderboard.name,
+      OUTBOX_TABLE_NAME      = aws_dynamodb_table.outbox.name,
+      PROFILE_TABLE_NAME     = aws_dynamodb_table.profile.name,
+      RATE_LIMIT_TABLE_NAME  = aws_dynamodb_table.rate_limit.name,
+      REGION_NAME            = local.region,
+      API_GATEWAY_URL        = aws_apigatewayv2_stage.websocket.invoke_url,
+      EVENT_SOURCE           = local.prefix,
+      METRICS_NAMESPACE      = local.prefix,
+      COGNITO_USER_POOL_ID   = aws_cognito_user_pool.users.id,
+      COGNITO_CLIENT_ID      = aws_cognito_user_pool_client.users.id,
     }
   }
 }
@@ -68,6 +180,14 @@ resource "aws_iam_role" "lambda" {
     name   = "ReadSqs"
     policy = data.aws_iam_policy_document.read_sqs.json
   }
+  inline_policy {
+    name   = "ReadTableStreams"
+    policy = data.aws_iam_policy_document.read_table_streams.json
+  }
+  inline_policy {
+    name   = "SendStreamFailures"
+    policy = data.aws_iam_policy_document.send_stream_failures.json
+  }
 }
 
 resource "aws_cloudwatch_log_group" "http" {
@@ -79,3 +199,18 @@ resource "aws_cloudwatch_log_group" "sqs" {
   name              = "/aws/lambda/${local.prefix}-SQS"
   retention_in_days = 90
 }
+
+resource "aws_cloudwatch_log_group" "sweep" {
+  name              = "/aws/lambda/${local.prefix}-Sweep"
+  retention_in_days = 90
+}
+
+resource "aws_cloudwatch_log_group" "outbox" {
+  name              = "/aws/lambda/${local.prefix}-Outbox"
+  retention_in_days = 90
+}
+
+resource "aws_cloudwatch_log_group" "game_stream" {
+  name              = "/aws/lambda/${local.prefix}-GameStream"
+  retention_in_days = 90
+}
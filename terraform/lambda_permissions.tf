@@ -13,6 +13,7 @@ data "aws_iam_policy_document" "lambda_assume_role" {
 data "aws_iam_policy_document" "access_dynamodb" {
   statement {
     actions = [
+      "dynamodb:BatchGetItem",
       "dynamodb:ConditionCheckItem",
       "dynamodb:DeleteItem",
       "dynamodb:GetItem",
@@ -25,7 +26,14 @@ data "aws_iam_policy_document" "access_dynamodb" {
     resources = [
       aws_dynamodb_table.database.arn,
       aws_dynamodb_table.game.arn,
+      "${aws_dynamodb_table.game.arn}/index/*",
+      aws_dynamodb_table.leaderboard.arn,
+      "${aws_dynamodb_table.leaderboard.arn}/index/*",
+      aws_dynamodb_table.outbox.arn,
+      aws_dynamodb_table.profile.arn,
+      aws_dynamodb_table.rate_limit.arn,
       aws_dynamodb_table.websocket_connection.arn,
+      "${aws_dynamodb_table.websocket_connection.arn}/index/*",
     ]
   }
 }
@@ -62,7 +70,35 @@ data "aws_iam_policy_document" "read_sqs" {
     ]
     effect = "Allow"
     resources = [
-      aws_sqs_queue.websocket_disconnected_queue.arn,
+      aws_sqs_queue.scheduled_commands.arn,
+    ]
+  }
+}
+
+data "aws_iam_policy_document" "send_stream_failures" {
+  statement {
+    actions = [
+      "sqs:SendMessage",
+    ]
+    effect = "Allow"
+    resources = [
+      aws_sqs_queue.outbox_failures.arn,
+    ]
+  }
+}
+
+data "aws_iam_policy_document" "read_table_streams" {
+  statement {
+    actions = [
+      "dynamodb:DescribeStream",
+      "dynamodb:GetRecords",
+      "dynamodb:GetShardIterator",
+      "dynamodb:ListStreams",
+    ]
+    effect = "Allow"
+    resources = [
+      aws_dynamodb_table.game.stream_arn,
+      aws_dynamodb_table.outbox.stream_arn,
     ]
   }
 }
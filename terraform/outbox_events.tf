@@ -0,0 +1,41 @@
+# Relay each outbox entry once the transaction that wrote it commits. Deletes
+# come through the stream too, the lambda skips them
+
+resource "aws_lambda_event_source_mapping" "relay_outbox" {
+  event_source_arn        = aws_dynamodb_table.outbox.stream_arn
+  function_name           = aws_lambda_function.outbox.function_name
+  starting_position       = "LATEST"
+  batch_size              = 10
+  maximum_retry_attempts  = 10
+  function_response_types = ["ReportBatchItemFailures"]
+  # Splits a failing batch so the entries either side of a bad one still go
+  bisect_batch_on_function_error = true
+  destination_config {
+    on_failure {
+      destination_arn = aws_sqs_queue.outbox_failures.arn
+    }
+  }
+}
+
+# Where to find the records the relay gave up on. The entries themselves stay
+# in the outbox table until their TTL
+
+resource "aws_sqs_queue" "outbox_failures" {
+  name                      = "${local.prefix}-OutboxFailures"
+  message_retention_seconds = 14 * 24 * 60 * 60
+}
+
+resource "aws_cloudwatch_metric_alarm" "outbox_failures" {
+  alarm_name          = "${local.prefix}-OutboxFailures"
+  alarm_description   = "The relay gave up on an outbox entry"
+  namespace           = "AWS/SQS"
+  metric_name         = "ApproximateNumberOfMessagesVisible"
+  dimensions          = { QueueName = aws_sqs_queue.outbox_failures.name }
+  statistic           = "Maximum"
+  period              = 5 * 60
+  evaluation_periods  = 1
+  comparison_operator = "GreaterThanThreshold"
+  threshold           = 0
+  treat_missing_data  = "notBreaching"
+  alarm_actions       = [aws_sns_topic.admin_email.arn]
+}
@@ -0,0 +1,90 @@
+# Scheduled commands are published as events
+
+resource "aws_cloudwatch_event_rule" "command_scheduled" {
+  name          = "${local.prefix}-CommandScheduled"
+  description   = "A command should run at a later time"
+  event_pattern = <<-EOF
+    {
+      "source": ["${local.prefix}.Scheduler"],
+      "detail-type": ["CommandScheduled"]
+    }
+  EOF
+}
+
+# Send the event to an SQS queue to act as a timer, commands that aren't due
+# yet are put back on the queue
+
+resource "aws_cloudwatch_event_target" "scheduled_commands_queue" {
+  rule      = aws_cloudwatch_event_rule.command_scheduled.name
+  target_id = "AddToSqs"
+  arn       = aws_sqs_queue.scheduled_commands.arn
+  retry_policy {
+    maximum_retry_attempts       = 0
+    maximum_event_age_in_seconds = 60
+  }
+}
+
+resource "aws_sqs_queue" "scheduled_commands" {
+  name                      = "${local.prefix}-ScheduledCommands"
+  delay_seconds             = 60
+  message_retention_seconds = 6 * 60 * 60
+  redrive_policy = jsonencode({
+    deadLetterTargetArn = aws_sqs_queue.scheduled_commands_dead_letter.arn
+    maxReceiveCount     = 3
+  })
+}
+
+# Messages that keep failing are moved here and kept for inspection
+
+resource "aws_sqs_queue" "scheduled_commands_dead_letter" {
+  name                      = "${local.prefix}-ScheduledCommands-DeadLetter"
+  message_retention_seconds = 14 * 24 * 60 * 60
+}
+
+resource "aws_cloudwatch_metric_alarm" "scheduled_commands_dead_letter" {
+  alarm_name          = "${local.prefix}-ScheduledCommandsDeadLetter"
+  alarm_description   = "A scheduled command kept failing"
+  namespace           = "AWS/SQS"
+  metric_name         = "ApproximateNumberOfMessagesVisible"
+  dimensions          = { QueueName = aws_sqs_queue.scheduled_commands_dead_letter.name }
+  statistic           = "Maximum"
+  period              = 5 * 60
+  evaluation_periods  = 1
+  comparison_operator = "GreaterThanThreshold"
+  threshold           = 0
+  treat_missing_data  = "notBreaching"
+  alarm_actions       = [aws_sns_topic.admin_email.arn]
+}
+
+resource "aws_sqs_queue_policy" "scheduled_commands" {
+  queue_url = aws_sqs_queue.scheduled_commands.id
+  policy    = data.aws_iam_policy_document.scheduled_commands.json
+}
+
+data "aws_iam_policy_document" "scheduled_commands" {
+  statement {
+    actions = [
+      "sqs:SendMessage",
+    ]
+    effect = "Allow"
+    resources = [
+      aws_sqs_queue.scheduled_commands.arn,
+    ]
+    principals {
+      type        = "Service"
+      identifiers = ["events.amazonaws.com"]
+    }
+    condition {
+      test     = "ArnEquals"
+      variable = "aws:SourceArn"
+      values   = [aws_cloudwatch_event_rule.command_scheduled.arn]
+    }
+  }
+}
+
+resource "aws_lambda_event_source_mapping" "run_scheduled_command" {
+  event_source_arn        = aws_sqs_queue.scheduled_commands.arn
+  function_name           = aws_lambda_function.sqs.function_name
+  batch_size              = 10
+  function_response_types = ["ReportBatchItemFailures"]
+}
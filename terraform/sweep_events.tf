@@ -0,0 +1,25 @@
+# Sweep up abandoned games, sessions and connections on a timer
+
+resource "aws_cloudwatch_event_rule" "sweep" {
+  name                = "${local.prefix}-Sweep"
+  description         = "Tear down items that have passed their expiry"
+  schedule_expression = "rate(15 minutes)"
+}
+
+resource "aws_cloudwatch_event_target" "sweep" {
+  rule      = aws_cloudwatch_event_rule.sweep.name
+  target_id = "RunSweeper"
+  arn       = aws_lambda_function.sweep.arn
+  retry_policy {
+    maximum_retry_attempts       = 0
+    maximum_event_age_in_seconds = 60
+  }
+}
+
+resource "aws_lambda_permission" "sweep" {
+  statement_id  = "AllowSweepSchedule"
+  action        = "lambda:InvokeFunction"
+  function_name = aws_lambda_function.sweep.function_name
+  principal     = "events.amazonaws.com"
+  source_arn    = aws_cloudwatch_event_rule.sweep.arn
+}